@@ -0,0 +1,269 @@
+//! [DID URL dereferencing](https://www.w3.org/TR/did-resolution/#dereferencing)
+//! results and metadata.
+
+use serde::Serialize;
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+use crate::{
+    ResolutionError,
+    did::Did,
+    did_url::DidUrl,
+    document::{
+        Document,
+        ServiceEndpoint,
+        VerificationMethodMap,
+    },
+    resolution::{
+        DocumentMetadata,
+        ResolutionResult,
+    },
+};
+
+/// The result of dereferencing a DID URL.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DereferencingResult {
+    pub dereferencing_metadata: DereferencingMetadata,
+    pub content_stream:         Content,
+    pub content_metadata:       DocumentMetadata,
+}
+
+/// The resource a DID URL dereferences to.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Content {
+    /// The DID document, for a DID URL without a fragment or service.
+    Document(Document),
+    /// A verification method selected by fragment.
+    VerificationMethod(Box<VerificationMethodMap>),
+    /// A service selected by fragment.
+    Service(ServiceEndpoint),
+    /// A service endpoint URL, selected by the `service` parameter and
+    /// optionally extended by `relativeRef`.
+    Url(String),
+}
+
+/// [DID URL dereferencing metadata](https://www.w3.org/TR/did-core/#did-url-dereferencing-metadata).
+#[skip_serializing_none]
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DereferencingMetadata {
+    /// Media type of the content stream.
+    pub content_type: Option<String>,
+    /// Error code, if dereferencing was unsuccessful.
+    pub error:        Option<String>,
+    /// Method-specific metadata.
+    #[serde(flatten)]
+    pub properties:   Map<String, Value>,
+}
+
+impl ResolutionResult {
+    /// Dereference a DID URL against this resolved document.
+    ///
+    /// A `service` parameter selects a service endpoint URL, which is
+    /// extended by the `relativeRef` parameter and fragment. Otherwise, a
+    /// fragment selects a verification method or service from the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DereferencingError::NotFound`] if nothing in the document
+    /// matches the DID URL.
+    pub fn dereference(self, url: &DidUrl) -> Result<DereferencingResult, DereferencingError> {
+        let document = self.document;
+
+        if url.did != document.id {
+            return Err(DereferencingError::InvalidDidUrl(
+                "DID does not match the resolved document".into(),
+            ));
+        }
+
+        // Paths are method-specific.
+        if url.path_abempty.is_some() {
+            return Err(DereferencingError::NotFound);
+        }
+
+        if let Some(service) = url.query_param("service") {
+            let endpoint = document
+                .service
+                .iter()
+                .flatten()
+                .find(|s| service_fragment(&s.id, &document.id) == Some(&service))
+                .and_then(|s| s.service_endpoint.first())
+                .ok_or(DereferencingError::NotFound)?;
+
+            let mut output = url
+                .query_param("relativeRef")
+                .map_or_else(|| endpoint.clone(), |r| resolve_reference(endpoint, &r));
+
+            if let Some(fragment) = &url.fragment
+                && !output.contains('#')
+            {
+                output.push('#');
+                output.push_str(fragment);
+            }
+
+            return Ok(DereferencingResult {
+                dereferencing_metadata: DereferencingMetadata {
+                    content_type: Some("text/uri-list".into()),
+                    ..Default::default()
+                },
+                content_stream:         Content::Url(output),
+                content_metadata:       DocumentMetadata::default(),
+            });
+        }
+
+        let dereferencing_metadata = DereferencingMetadata {
+            content_type: Some(
+                self.resolution_metadata
+                    .content_type
+                    .unwrap_or_else(|| "application/did+json".into()),
+            ),
+            ..Default::default()
+        };
+
+        let Some(fragment) = &url.fragment else {
+            return Ok(DereferencingResult {
+                dereferencing_metadata,
+                content_stream: Content::Document(document),
+                content_metadata: self.document_metadata,
+            });
+        };
+
+        let id = DidUrl {
+            did:          document.id.clone(),
+            path_abempty: None,
+            query:        None,
+            fragment:     Some(fragment.clone()),
+        };
+
+        let content = document
+            .find_verification_method(&id)
+            .map(|method| Content::VerificationMethod(Box::new(method)))
+            .or_else(|| {
+                document
+                    .service
+                    .iter()
+                    .flatten()
+                    .find(|s| service_fragment(&s.id, &document.id) == Some(fragment))
+                    .cloned()
+                    .map(Content::Service)
+            })
+            .ok_or(DereferencingError::NotFound)?;
+
+        Ok(DereferencingResult {
+            dereferencing_metadata,
+            content_stream: content,
+            content_metadata: self.document_metadata,
+        })
+    }
+}
+
+/// The fragment of a service `id`, which may be relative (`#name`) or an
+/// absolute DID URL of the document.
+fn service_fragment<'a>(id: &'a str, did: &Did) -> Option<&'a str> {
+    let did = did.to_string();
+    id.strip_prefix(&did).unwrap_or(id).strip_prefix('#')
+}
+
+/// Resolves a relative reference against a base URL, following
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.2). Dot
+/// segments are not removed.
+fn resolve_reference(base: &str, reference: &str) -> String {
+    let base = base.split_once('#').map_or(base, |(base, _)| base);
+
+    let has_scheme = reference
+        .find([':', '/', '?', '#'])
+        .is_some_and(|i| i > 0 && reference.as_bytes()[i] == b':');
+    if has_scheme {
+        return reference.to_string();
+    }
+
+    let scheme_end = base.find("://").map_or(0, |i| i + 3);
+    let authority_end = base[scheme_end..]
+        .find(['/', '?'])
+        .map_or(base.len(), |i| scheme_end + i);
+    let path_end = base[authority_end..]
+        .find('?')
+        .map_or(base.len(), |i| authority_end + i);
+
+    if let Some(reference) = reference.strip_prefix("//") {
+        return format!("{}{reference}", &base[..scheme_end]);
+    }
+
+    if reference.starts_with('/') {
+        format!("{}{reference}", &base[..authority_end])
+    } else if reference.starts_with('?') {
+        format!("{}{reference}", &base[..path_end])
+    } else if reference.is_empty() || reference.starts_with('#') {
+        format!("{base}{reference}")
+    } else {
+        let path = &base[authority_end..path_end];
+        let dir = path.rfind('/').map_or("/", |i| &path[..=i]);
+        format!("{}{dir}{reference}", &base[..authority_end])
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DereferencingError {
+    #[error(transparent)]
+    Resolution(#[from] ResolutionError),
+    #[error("invalid DID URL: {0}")]
+    InvalidDidUrl(String),
+    #[error("not found")]
+    NotFound,
+}
+
+impl DereferencingError {
+    /// The [DID URL dereferencing error code](https://www.w3.org/TR/did-resolution/#errors).
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::Resolution(e) => e.code(),
+            Self::InvalidDidUrl(_) => "invalidDidUrl",
+            Self::NotFound => "notFound",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_reference() {
+        let base = "https://agent.example.com/8377464?a=1#x";
+        assert_eq!(
+            resolve_reference(base, "/credentials"),
+            "https://agent.example.com/credentials"
+        );
+        assert_eq!(
+            resolve_reference(base, "records/1"),
+            "https://agent.example.com/records/1"
+        );
+        assert_eq!(
+            resolve_reference(base, "?b=2"),
+            "https://agent.example.com/8377464?b=2"
+        );
+        assert_eq!(
+            resolve_reference(base, "#y"),
+            "https://agent.example.com/8377464?a=1#y"
+        );
+        assert_eq!(
+            resolve_reference(base, "//other.example/z"),
+            "https://other.example/z"
+        );
+        assert_eq!(
+            resolve_reference(base, "https://other.example/"),
+            "https://other.example/"
+        );
+        assert_eq!(
+            resolve_reference("https://example.com", "a"),
+            "https://example.com/a"
+        );
+    }
+}
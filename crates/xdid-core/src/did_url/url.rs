@@ -19,6 +19,7 @@ use crate::{
     uri::{
         Segment,
         is_segment,
+        percent_decode,
     },
 };
 
@@ -71,6 +72,18 @@ impl DidUrl {
             query:    self.query.clone(),
         })
     }
+
+    /// Returns the percent-decoded value of a query parameter, such as the
+    /// `service` or `versionId` [DID parameters](https://www.w3.org/TR/did-core/#did-parameters).
+    #[must_use]
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        self.query
+            .as_deref()?
+            .split('&')
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+            .find(|(key, _)| percent_decode(key).as_deref() == Some(name))
+            .and_then(|(_, value)| percent_decode(value))
+    }
 }
 
 impl Display for DidUrl {
@@ -267,4 +280,29 @@ mod tests {
         let deserialized = DidUrl::from_str(&serialized).expect("deserialize failed");
         assert_eq!(deserialized, did_url);
     }
+
+    #[test]
+    fn test_query_param() {
+        let did_url =
+            DidUrl::from_str("did:example:123?service=my-service&relativeRef=/records/abc123")
+                .expect("valid DID URL");
+
+        assert_eq!(
+            did_url.query_param("service").as_deref(),
+            Some("my-service")
+        );
+        assert_eq!(
+            did_url.query_param("relativeRef").as_deref(),
+            Some("/records/abc123")
+        );
+        assert_eq!(did_url.query_param("versionId"), None);
+    }
+
+    #[test]
+    fn test_query_param_decode() {
+        let did_url =
+            DidUrl::from_str("did:example:123?relativeRef=%2Fa%20b&flag").expect("valid DID URL");
+        assert_eq!(did_url.query_param("relativeRef").as_deref(), Some("/a b"));
+        assert_eq!(did_url.query_param("flag").as_deref(), Some(""));
+    }
 }
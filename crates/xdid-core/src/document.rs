@@ -18,7 +18,7 @@ use crate::{
 };
 
 #[skip_serializing_none]
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 #[serde_as]
 pub struct Document {
@@ -36,6 +36,23 @@ pub struct Document {
 }
 
 impl Document {
+    /// Creates a document with the given `id`, and no other properties.
+    #[must_use]
+    pub const fn new(id: Did) -> Self {
+        Self {
+            id,
+            also_known_as: None,
+            controller: None,
+            verification_method: None,
+            authentication: None,
+            assertion_method: None,
+            key_agreement: None,
+            capability_invocation: None,
+            capability_delegation: None,
+            service: None,
+        }
+    }
+
     /// Returns the verification method that the provided [`DidUrl`] is
     /// referencing, restricted to a given [`VerificationRole`].
     #[must_use]
@@ -84,12 +101,40 @@ impl Document {
                 {
                     return self.resolve_relative_url(&relative_url);
                 }
-                // TODO: Support additional DID resolution?
+                // Other documents must be resolved, see
+                // `DidResolver::resolve_verification_method` in xdid.
                 None
             }
         }
     }
 
+    /// Finds a verification method by its `id`, either in
+    /// `verificationMethod` or embedded in a verification relationship.
+    #[must_use]
+    pub fn find_verification_method(&self, id: &DidUrl) -> Option<VerificationMethodMap> {
+        let embedded = [
+            &self.authentication,
+            &self.assertion_method,
+            &self.key_agreement,
+            &self.capability_invocation,
+            &self.capability_delegation,
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|method| match method {
+            VerificationMethod::Map(map) => Some(map.as_ref()),
+            _ => None,
+        });
+
+        self.verification_method
+            .iter()
+            .flatten()
+            .chain(embedded)
+            .find(|method| method.id == *id)
+            .cloned()
+    }
+
     fn resolve_relative_url(&self, url: &RelativeDidUrl) -> Option<VerificationMethodMap> {
         for method in self.verification_method.as_deref().unwrap_or_default() {
             if method.id.to_relative().as_ref() == Some(url) {
@@ -0,0 +1,117 @@
+//! [JSON Canonicalization Scheme](https://www.rfc-editor.org/rfc/rfc8785)
+//! (JCS).
+//!
+//! Produces the same bytes for the same JSON data regardless of member order
+//! or number formatting, for use in signing and hashing.
+
+use serde::Serialize;
+use serde_json::{
+    Number,
+    Value,
+};
+use thiserror::Error;
+
+/// Serialize a value as canonical JSON.
+///
+/// # Errors
+///
+/// Returns an error if the value cannot be represented as JSON, or contains
+/// an integer that is not exactly representable as an IEEE 754 double.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, JcsError> {
+    canonicalize(&serde_json::to_value(value)?)
+}
+
+/// Serialize a value as canonical JSON bytes.
+///
+/// # Errors
+///
+/// See [`to_string`].
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, JcsError> {
+    to_string(value).map(String::into_bytes)
+}
+
+/// Canonicalize a JSON value.
+///
+/// # Errors
+///
+/// Returns an error if the value contains an integer that is not exactly
+/// representable as an IEEE 754 double.
+pub fn canonicalize(value: &Value) -> Result<String, JcsError> {
+    let mut out = String::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<(), JcsError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(true) => out.push_str("true"),
+        Value::Bool(false) => out.push_str("false"),
+        Value::Number(number) => out.push_str(&format_number(number)?),
+        Value::String(string) => out.push_str(&serde_json::to_string(string)?),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, value)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // Members are sorted by their UTF-16 code units.
+            let mut members = map.iter().collect::<Vec<_>>();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key)?);
+                out.push(':');
+                write_value(out, value)?;
+            }
+            out.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn format_number(number: &Number) -> Result<String, JcsError> {
+    let value = if let Some(n) = number.as_u64() {
+        let value = n as f64;
+        if value as u128 != u128::from(n) {
+            return Err(JcsError::InvalidNumber(number.clone()));
+        }
+        value
+    } else if let Some(n) = number.as_i64() {
+        let value = n as f64;
+        if value as i128 != i128::from(n) {
+            return Err(JcsError::InvalidNumber(number.clone()));
+        }
+        value
+    } else {
+        number
+            .as_f64()
+            .ok_or_else(|| JcsError::InvalidNumber(number.clone()))?
+    };
+
+    // Formats as ECMAScript's `Number.prototype.toString`.
+    Ok(ryu_js::Buffer::new().format_finite(value).to_string())
+}
+
+#[derive(Error, Debug)]
+pub enum JcsError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("number not representable as an IEEE 754 double: {0}")]
+    InvalidNumber(Number),
+}
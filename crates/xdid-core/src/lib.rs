@@ -3,14 +3,22 @@
 use std::{
     future::Future,
     pin::Pin,
+    sync::Arc,
 };
 
 use did::Did;
+use resolution::{
+    ResolutionOptions,
+    ResolutionResult,
+};
 use thiserror::Error;
 
+pub mod dereferencing;
 pub mod did;
 pub mod did_url;
 pub mod document;
+pub mod jcs;
+pub mod resolution;
 mod uri;
 
 /// Boxed future for [`Method::resolve`].
@@ -19,14 +27,89 @@ pub type MethodFuture<T> = Pin<Box<dyn Future<Output = T> + Send + Sync>>;
 pub trait Method: Send + Sync {
     fn method_name(&self) -> &'static str;
     fn resolve(&self, did: Did) -> MethodFuture<Result<document::Document, ResolutionError>>;
+
+    /// Resolve a DID along with resolution and document metadata. Methods that
+    /// track metadata, such as deactivation or versions, should override this.
+    ///
+    /// By default, options selecting a version are rejected with
+    /// [`ResolutionError::InvalidOptions`], and method-specific options are
+    /// ignored.
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        let fut = self.resolve(did);
+        Box::pin(async move {
+            options.reject_versions()?;
+            fut.await.map(ResolutionResult::from)
+        })
+    }
 }
 
-#[derive(Error, Debug)]
+/// Source of a [`ResolutionError`], shared so the error can be cloned.
+pub type ErrorSource = Arc<dyn std::error::Error + Send + Sync>;
+
+/// An error from DID resolution, following the
+/// [DID Resolution error codes](https://www.w3.org/TR/did-resolution/#errors).
+#[derive(Error, Debug, Clone)]
 pub enum ResolutionError {
     #[error("invalid DID")]
-    InvalidDid,
-    #[error("resolution failed: {0}")]
-    ResolutionFailed(String),
-    #[error("unsupported method")]
-    UnsupportedMethod,
+    InvalidDid(#[source] Option<ErrorSource>),
+    #[error("DID not found")]
+    NotFound(#[source] Option<ErrorSource>),
+    #[error("method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("representation not supported")]
+    RepresentationNotSupported,
+    #[error("invalid DID document")]
+    InvalidDidDocument(#[source] ErrorSource),
+    #[error("internal error")]
+    InternalError(#[source] ErrorSource),
+    #[error("DID has been deactivated")]
+    Deactivated,
+    #[error("invalid resolution options: {0}")]
+    InvalidOptions(String),
+    #[error("resolution timed out")]
+    Timeout,
+}
+
+impl ResolutionError {
+    pub fn invalid_did(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::InvalidDid(Some(source.into().into()))
+    }
+
+    pub fn not_found(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::NotFound(Some(source.into().into()))
+    }
+
+    pub fn invalid_did_document(
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::InvalidDidDocument(source.into().into())
+    }
+
+    pub fn internal(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::InternalError(source.into().into())
+    }
+
+    /// The error code, as used in [`ResolutionMetadata::error`] and HTTP
+    /// resolver responses.
+    ///
+    /// [`Self::Timeout`] has no dedicated code, and maps to `internalError`.
+    ///
+    /// [`ResolutionMetadata::error`]: resolution::ResolutionMetadata::error
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidDid(_) => "invalidDid",
+            Self::NotFound(_) => "notFound",
+            Self::MethodNotSupported(_) => "methodNotSupported",
+            Self::RepresentationNotSupported => "representationNotSupported",
+            Self::InvalidDidDocument(_) => "invalidDidDocument",
+            Self::InternalError(_) | Self::Timeout => "internalError",
+            Self::Deactivated => "deactivated",
+            Self::InvalidOptions(_) => "invalidOptions",
+        }
+    }
 }
@@ -0,0 +1,167 @@
+//! [DID resolution](https://www.w3.org/TR/did-resolution/#did-resolution-result)
+//! results and metadata.
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::skip_serializing_none;
+
+use crate::{
+    ResolutionError,
+    did::Did,
+    document::Document,
+};
+
+/// Media types of the JSON representations a [`Document`] can be resolved
+/// as.
+pub const JSON_MEDIA_TYPES: &[&str] = &[
+    "application/did+json",
+    "application/did+ld+json",
+    "application/json",
+];
+
+/// [DID resolution options](https://www.w3.org/TR/did-resolution/#did-resolution-options).
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionOptions {
+    /// Media type of the preferred representation.
+    pub accept:       Option<String>,
+    /// Resolve a specific version of the document.
+    pub version_id:   Option<String>,
+    /// Resolve the version of the document that was valid at this time, as
+    /// an XML Schema `dateTime` string.
+    pub version_time: Option<String>,
+    /// Method-specific options.
+    #[serde(flatten)]
+    pub properties:   Map<String, Value>,
+}
+
+impl ResolutionOptions {
+    /// Check that the options do not select a version, for methods that can
+    /// only resolve the latest document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResolutionError::InvalidOptions`] if `versionId` or
+    /// `versionTime` is set.
+    pub fn reject_versions(&self) -> Result<(), ResolutionError> {
+        if self.version_id.is_some() {
+            return Err(ResolutionError::InvalidOptions(
+                "versionId is not supported".into(),
+            ));
+        }
+        if self.version_time.is_some() {
+            return Err(ResolutionError::InvalidOptions(
+                "versionTime is not supported".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether the `accept` option allows a JSON representation.
+    #[must_use]
+    pub fn accepts_json(&self) -> bool {
+        self.accept.as_deref().is_none_or(|accept| {
+            accept.split(',').any(|media_type| {
+                let media_type = media_type.split(';').next().unwrap_or_default().trim();
+                media_type == "*/*" || JSON_MEDIA_TYPES.contains(&media_type)
+            })
+        })
+    }
+}
+
+/// The result of resolving a DID, including metadata about the resolution
+/// process and the document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResult {
+    #[serde(rename = "didDocument")]
+    pub document:            Document,
+    #[serde(rename = "didResolutionMetadata", default)]
+    pub resolution_metadata: ResolutionMetadata,
+    #[serde(rename = "didDocumentMetadata", default)]
+    pub document_metadata:   DocumentMetadata,
+}
+
+impl From<Document> for ResolutionResult {
+    fn from(document: Document) -> Self {
+        Self {
+            document,
+            resolution_metadata: ResolutionMetadata::default(),
+            document_metadata: DocumentMetadata::default(),
+        }
+    }
+}
+
+/// [DID resolution metadata](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMetadata {
+    /// Media type of the returned representation, such as
+    /// `application/did+json`.
+    pub content_type: Option<String>,
+    /// Error code, if resolution was unsuccessful.
+    pub error:        Option<String>,
+    /// Method-specific metadata.
+    #[serde(flatten)]
+    pub properties:   Map<String, Value>,
+}
+
+impl ResolutionMetadata {
+    /// Property holding the HTTP `Cache-Control` header of the response, for
+    /// methods that resolve over HTTP.
+    pub const CACHE_CONTROL: &str = "cacheControl";
+
+    /// The HTTP `Cache-Control` header of the response, if provided by the
+    /// method.
+    #[must_use]
+    pub fn cache_control(&self) -> Option<&str> {
+        self.properties
+            .get(Self::CACHE_CONTROL)
+            .and_then(Value::as_str)
+    }
+}
+
+impl From<&ResolutionError> for ResolutionMetadata {
+    fn from(value: &ResolutionError) -> Self {
+        Self {
+            error: Some(value.code().to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// [DID document metadata](https://www.w3.org/TR/did-core/#did-document-metadata).
+///
+/// Timestamps are XML Schema `dateTime` strings, normalized to UTC.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMetadata {
+    pub created:         Option<String>,
+    pub updated:         Option<String>,
+    pub deactivated:     Option<bool>,
+    pub next_update:     Option<String>,
+    pub version_id:      Option<String>,
+    pub next_version_id: Option<String>,
+    pub equivalent_id:   Option<Vec<Did>>,
+    pub canonical_id:    Option<Did>,
+    /// Method-specific metadata.
+    #[serde(flatten)]
+    pub properties:      Map<String, Value>,
+}
+
+impl DocumentMetadata {
+    /// Whether the DID has been deactivated.
+    #[must_use]
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated.unwrap_or_default()
+    }
+}
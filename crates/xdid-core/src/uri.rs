@@ -90,6 +90,25 @@ const fn is_sub_delim(c: char) -> bool {
         || c == '='
 }
 
+/// Decodes `pct-encoded` octets. Returns `None` if an escape is malformed, or
+/// the decoded bytes are not UTF-8.
+pub fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hi = char::from(iter.next()?).to_digit(16)?;
+            let lo = char::from(iter.next()?).to_digit(16)?;
+            bytes.push(u8::try_from(hi << 4 | lo).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +148,13 @@ mod tests {
         assert!(!is_segment("%3%f9a", Segment::Base));
         assert!(!is_segment("%%f9a", Segment::Base));
     }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%2Fb%3a").as_deref(), Some("a/b:"));
+        assert_eq!(percent_decode("%E2%82%AC").as_deref(), Some("\u{20ac}"));
+        assert_eq!(percent_decode("%2"), None);
+        assert_eq!(percent_decode("%zz"), None);
+        assert_eq!(percent_decode("%ff"), None);
+    }
 }
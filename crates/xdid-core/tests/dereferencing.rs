@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use serde_json::json;
+use xdid_core::{
+    dereferencing::{
+        Content,
+        DereferencingError,
+    },
+    did_url::DidUrl,
+    document::Document,
+    resolution::ResolutionResult,
+};
+
+fn result() -> ResolutionResult {
+    let document: Document = serde_json::from_value(json!({
+        "id": "did:example:123",
+        "verificationMethod": [{
+            "id": "did:example:123#key-1",
+            "type": "Multikey",
+            "controller": "did:example:123",
+            "publicKeyMultibase": "z6MkmM42vxfqZQsv4ehtTjFFxQ4sQKS2w6WR7emozFAn5cxu"
+        }],
+        "authentication": [{
+            "id": "did:example:123#key-2",
+            "type": "Multikey",
+            "controller": "did:example:123",
+            "publicKeyMultibase": "z6MkmM42vxfqZQsv4ehtTjFFxQ4sQKS2w6WR7emozFAn5cxu"
+        }],
+        "service": [
+            {
+                "id": "#agent",
+                "type": ["DIDCommMessaging"],
+                "serviceEndpoint": ["https://agent.example.com/8377464"]
+            },
+            {
+                "id": "did:example:123#files",
+                "type": ["LinkedDomains"],
+                "serviceEndpoint": ["https://files.example.com/"]
+            }
+        ]
+    }))
+    .expect("deserialization should succeed");
+
+    ResolutionResult::from(document)
+}
+
+fn dereference(url: &str) -> Result<Content, DereferencingError> {
+    let url = DidUrl::from_str(url).expect("valid DID URL");
+    result().dereference(&url).map(|r| r.content_stream)
+}
+
+#[test]
+fn test_dereference_document() {
+    let content = dereference("did:example:123").expect("dereferencing should succeed");
+    assert!(matches!(content, Content::Document(doc) if doc.id.to_string() == "did:example:123"));
+}
+
+#[test]
+fn test_dereference_verification_method() {
+    let content = dereference("did:example:123#key-1").expect("dereferencing should succeed");
+    assert!(
+        matches!(content, Content::VerificationMethod(vm) if vm.id.to_string() == "did:example:123#key-1")
+    );
+
+    let content = dereference("did:example:123#key-2").expect("embedded method should be found");
+    assert!(matches!(content, Content::VerificationMethod(_)));
+}
+
+#[test]
+fn test_dereference_service_fragment() {
+    let content = dereference("did:example:123#agent").expect("dereferencing should succeed");
+    assert!(matches!(content, Content::Service(s) if s.id == "#agent"));
+}
+
+#[test]
+fn test_dereference_service_ref() {
+    let content = dereference("did:example:123?service=agent&relativeRef=%2Fcredentials#degree")
+        .expect("dereferencing should succeed");
+    assert_eq!(
+        content,
+        Content::Url("https://agent.example.com/credentials#degree".to_string())
+    );
+
+    let content = dereference("did:example:123?service=files&relativeRef=/records/abc123")
+        .expect("dereferencing should succeed");
+    assert_eq!(
+        content,
+        Content::Url("https://files.example.com/records/abc123".to_string())
+    );
+
+    let content =
+        dereference("did:example:123?service=files").expect("dereferencing should succeed");
+    assert_eq!(
+        content,
+        Content::Url("https://files.example.com/".to_string())
+    );
+}
+
+#[test]
+fn test_dereference_not_found() {
+    for url in [
+        "did:example:123#missing",
+        "did:example:123?service=missing",
+        "did:example:123/path",
+    ] {
+        assert!(
+            matches!(dereference(url), Err(DereferencingError::NotFound)),
+            "{url}"
+        );
+    }
+
+    assert!(matches!(
+        dereference("did:example:456#key-1"),
+        Err(DereferencingError::InvalidDidUrl(_))
+    ));
+}
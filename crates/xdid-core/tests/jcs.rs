@@ -0,0 +1,114 @@
+use serde_json::{
+    Value,
+    json,
+};
+use xdid_core::jcs;
+
+fn canonicalize(raw: &str) -> String {
+    let value: Value = serde_json::from_str(raw).expect("deserialization should succeed");
+    jcs::canonicalize(&value).expect("canonicalization should succeed")
+}
+
+/// [RFC 8785 section 3.2.2](https://www.rfc-editor.org/rfc/rfc8785#section-3.2.2).
+#[test]
+fn test_serialization() {
+    let raw = r#"{
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+        "literals": [null, true, false]
+    }"#;
+
+    assert_eq!(
+        canonicalize(raw),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+/// [RFC 8785 section 3.2.3](https://www.rfc-editor.org/rfc/rfc8785#section-3.2.3).
+#[test]
+fn test_sorting() {
+    let raw = r#"{
+        "\u20ac": "Euro Sign",
+        "\r": "Carriage Return",
+        "\ufb33": "Hebrew Letter Dalet With Dagesh",
+        "1": "One",
+        "\ud83d\ude00": "Emoji: Grinning Face",
+        "\u0080": "Control",
+        "\u00f6": "Latin Small Letter O With Diaeresis"
+    }"#;
+
+    assert_eq!(
+        canonicalize(raw),
+        "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\
+         \"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",\
+         \"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+    );
+}
+
+/// [RFC 8785 appendix B](https://www.rfc-editor.org/rfc/rfc8785#appendix-B).
+#[test]
+fn test_numbers() {
+    const VECTORS: &[(u64, &str)] = &[
+        (0x0000_0000_0000_0000, "0"),
+        (0x8000_0000_0000_0000, "0"),
+        (0x0000_0000_0000_0001, "5e-324"),
+        (0x8000_0000_0000_0001, "-5e-324"),
+        (0x7FEF_FFFF_FFFF_FFFF, "1.7976931348623157e+308"),
+        (0xFFEF_FFFF_FFFF_FFFF, "-1.7976931348623157e+308"),
+        (0x4340_0000_0000_0000, "9007199254740992"),
+        (0xC340_0000_0000_0000, "-9007199254740992"),
+        (0x4430_0000_0000_0000, "295147905179352830000"),
+        (0x44B5_2D02_C7E1_4AF5, "9.999999999999997e+22"),
+        (0x44B5_2D02_C7E1_4AF6, "1e+23"),
+        (0x44B5_2D02_C7E1_4AF7, "1.0000000000000001e+23"),
+        (0x444B_1AE4_D6E2_EF4E, "999999999999999700000"),
+        (0x444B_1AE4_D6E2_EF4F, "999999999999999900000"),
+        (0x444B_1AE4_D6E2_EF50, "1e+21"),
+        (0x3EB0_C6F7_A0B5_ED8C, "9.999999999999997e-7"),
+        (0x3EB0_C6F7_A0B5_ED8D, "0.000001"),
+        (0x41B3_DE43_5555_5553, "333333333.3333332"),
+        (0x41B3_DE43_5555_5554, "333333333.33333325"),
+        (0x41B3_DE43_5555_5555, "333333333.3333333"),
+        (0x41B3_DE43_5555_5556, "333333333.3333334"),
+        (0x41B3_DE43_5555_5557, "333333333.33333343"),
+        (0xBECB_F647_612F_3696, "-0.0000033333333333333333"),
+        (0x4314_3FF3_C1CB_0959, "1424953923781206.2"),
+    ];
+
+    for (bits, expected) in VECTORS {
+        let value = json!(f64::from_bits(*bits));
+        assert_eq!(
+            jcs::canonicalize(&value).expect("canonicalization should succeed"),
+            *expected,
+            "{bits:#x}"
+        );
+    }
+}
+
+#[test]
+fn test_integers() {
+    assert_eq!(
+        canonicalize("[1, -1, 100, 9007199254740992]"),
+        "[1,-1,100,9007199254740992]"
+    );
+    assert!(jcs::canonicalize(&json!(9_007_199_254_740_993_u64)).is_err());
+    assert!(jcs::canonicalize(&json!(u64::MAX)).is_err());
+}
+
+#[test]
+fn test_serialize() {
+    #[derive(serde::Serialize)]
+    struct Example {
+        b: u8,
+        a: Vec<&'static str>,
+    }
+
+    let example = Example {
+        b: 2,
+        a: vec!["x", "y"],
+    };
+    assert_eq!(
+        jcs::to_string(&example).expect("canonicalization should succeed"),
+        r#"{"a":["x","y"],"b":2}"#
+    );
+}
@@ -0,0 +1,139 @@
+use serde_json::{
+    Value,
+    json,
+};
+use xdid_core::{
+    ResolutionError,
+    resolution::{
+        ResolutionMetadata,
+        ResolutionOptions,
+        ResolutionResult,
+    },
+};
+
+#[test]
+fn test_resolution_result_serde() {
+    let expected = json!({
+        "didDocument": {
+            "id": "did:example:123456789abcdefghi"
+        },
+        "didResolutionMetadata": {
+            "contentType": "application/did+json"
+        },
+        "didDocumentMetadata": {
+            "created": "2019-03-23T06:35:22Z",
+            "updated": "2023-08-10T13:40:06Z",
+            "deactivated": true,
+            "versionId": "2",
+            "equivalentId": ["did:example:abcdefghi123456789"],
+            "method": { "published": true }
+        }
+    });
+
+    let result: ResolutionResult =
+        serde_json::from_value(expected.clone()).expect("deserialization should succeed");
+
+    assert!(result.document_metadata.is_deactivated());
+    assert_eq!(result.document_metadata.version_id.as_deref(), Some("2"));
+    assert_eq!(
+        result.resolution_metadata.content_type.as_deref(),
+        Some("application/did+json")
+    );
+    assert!(result.document_metadata.properties.contains_key("method"));
+
+    let actual: Value = serde_json::to_value(&result).expect("serialization should succeed");
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_resolution_result_default_metadata() {
+    let result: ResolutionResult = serde_json::from_value(json!({
+        "didDocument": { "id": "did:example:123" }
+    }))
+    .expect("deserialization should succeed");
+
+    assert!(!result.document_metadata.is_deactivated());
+    assert_eq!(result.resolution_metadata.error, None);
+}
+
+#[test]
+fn test_resolution_options_accept() {
+    let accepts = |accept: &str| {
+        ResolutionOptions {
+            accept: Some(accept.to_string()),
+            ..Default::default()
+        }
+        .accepts_json()
+    };
+
+    assert!(ResolutionOptions::default().accepts_json());
+    assert!(accepts("application/did+json"));
+    assert!(accepts(
+        "application/did+ld+json;profile=\"https://w3id.org/did-resolution\""
+    ));
+    assert!(accepts("application/did+cbor, */*;q=0.1"));
+    assert!(!accepts("application/did+cbor"));
+}
+
+#[test]
+fn test_resolution_options_versions() {
+    ResolutionOptions::default()
+        .reject_versions()
+        .expect("no version should be selected");
+
+    let options: ResolutionOptions = serde_json::from_value(json!({
+        "versionTime": "2021-05-10T17:00:00Z",
+        "custom": 1
+    }))
+    .expect("deserialization should succeed");
+
+    assert!(options.reject_versions().is_err());
+    assert!(options.properties.contains_key("custom"));
+}
+
+#[test]
+fn test_error_codes() {
+    let json_error = serde_json::from_str::<Value>("{").expect_err("JSON should be invalid");
+    let errors = [
+        (ResolutionError::InvalidDid(None), "invalidDid"),
+        (ResolutionError::not_found("missing"), "notFound"),
+        (
+            ResolutionError::MethodNotSupported("example".to_string()),
+            "methodNotSupported",
+        ),
+        (
+            ResolutionError::RepresentationNotSupported,
+            "representationNotSupported",
+        ),
+        (
+            ResolutionError::invalid_did_document(json_error),
+            "invalidDidDocument",
+        ),
+        (ResolutionError::internal("failed"), "internalError"),
+        (ResolutionError::Deactivated, "deactivated"),
+        (
+            ResolutionError::InvalidOptions("versionId".to_string()),
+            "invalidOptions",
+        ),
+    ];
+
+    for (error, code) in errors {
+        assert_eq!(error.code(), code);
+        assert_eq!(
+            ResolutionMetadata::from(&error).error.as_deref(),
+            Some(code)
+        );
+    }
+}
+
+#[test]
+fn test_error_source() {
+    let json_error = serde_json::from_str::<Value>("{").expect_err("JSON should be invalid");
+    let message = json_error.to_string();
+
+    let error = ResolutionError::invalid_did_document(json_error);
+    let source = std::error::Error::source(&error).expect("source should be kept");
+    assert_eq!(source.to_string(), message);
+
+    assert!(std::error::Error::source(&ResolutionError::InvalidDid(None)).is_none());
+}
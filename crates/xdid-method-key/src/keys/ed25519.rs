@@ -0,0 +1,268 @@
+use ed25519_dalek::{
+    Signature,
+    SigningKey,
+    VerifyingKey,
+    pkcs8::{
+        DecodePrivateKey,
+        EncodePrivateKey,
+        spki::der::pem::LineEnding,
+    },
+};
+use jose_jwk::{
+    Jwk,
+    Key,
+    Okp,
+    OkpCurves,
+    Parameters,
+    jose_jwa::Signing,
+};
+use rand_core::OsRng;
+use zeroize::Zeroizing;
+
+use super::{
+    DidKeyPair,
+    KeyParser,
+    Multicodec,
+    PublicKey,
+    Signer,
+    Verifier,
+    WithMulticodec,
+};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ed25519KeyPair(SigningKey);
+
+impl DidKeyPair for Ed25519KeyPair {
+    fn generate() -> Self {
+        let mut rng = OsRng;
+        let secret = SigningKey::generate(&mut rng);
+        Self(secret)
+    }
+
+    fn public(&self) -> impl PublicKey {
+        Ed25519PublicKey(self.0.verifying_key())
+    }
+
+    fn to_pkcs8_pem(&self) -> anyhow::Result<Zeroizing<String>> {
+        let pem = self.0.to_pkcs8_pem(LineEnding::LF)?;
+        Ok(pem)
+    }
+    fn from_pkcs8_pem(pem: &str) -> anyhow::Result<Self> {
+        let key = SigningKey::from_pkcs8_pem(pem)?;
+        Ok(Self(key))
+    }
+}
+
+impl Signer for Ed25519KeyPair {
+    fn sign(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let sig = ed25519_dalek::Signer::sign(&self.0, message);
+        Ok(sig.to_bytes().to_vec())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Ed25519PublicKey(VerifyingKey);
+
+impl PublicKey for Ed25519PublicKey {
+    fn to_sec1_bytes(&self) -> Box<[u8]> {
+        self.0.as_bytes().as_slice().into()
+    }
+    fn to_encoded_point_bytes(&self) -> Box<[u8]> {
+        self.0.as_bytes().as_slice().into()
+    }
+
+    fn to_jwk(&self) -> Jwk {
+        Jwk {
+            key: Key::Okp(Okp {
+                crv: OkpCurves::Ed25519,
+                x:   self.0.as_bytes().to_vec().into(),
+                d:   None,
+            }),
+            prm: Parameters::default(),
+        }
+    }
+
+    fn jws_alg(&self) -> Option<Signing> {
+        Some(Signing::EdDsa)
+    }
+
+    #[cfg(feature = "x25519")]
+    fn to_key_agreement(&self) -> Option<Box<dyn PublicKey>> {
+        let point = self.0.to_montgomery();
+        Some(Box::new(super::x25519::X25519PublicKey(point.to_bytes())))
+    }
+}
+
+impl Verifier for Ed25519PublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_slice(signature)?;
+        self.0.verify_strict(message, &sig)?;
+        Ok(())
+    }
+}
+
+impl WithMulticodec for Ed25519PublicKey {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(Ed25519Codec)
+    }
+}
+
+pub(crate) struct Ed25519KeyParser;
+
+impl KeyParser for Ed25519KeyParser {
+    fn parse(&self, public_key: Vec<u8>) -> Result<Box<dyn PublicKey>, crate::parser::ParseError> {
+        let bytes: [u8; 32] = public_key
+            .try_into()
+            .map_err(|_| crate::parser::ParseError::InvalidPublicKey)?;
+        let key = VerifyingKey::from_bytes(&bytes)
+            .map_err(|_| crate::parser::ParseError::InvalidPublicKey)?;
+        Ok(Box::new(Ed25519PublicKey(key)))
+    }
+
+    fn parse_jwk(
+        &self,
+        jwk: &Jwk,
+    ) -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>> {
+        match &jwk.key {
+            Key::Okp(okp) if okp.crv == OkpCurves::Ed25519 => Some(self.parse(okp.x.to_vec())),
+            _ => None,
+        }
+    }
+}
+
+impl WithMulticodec for Ed25519KeyParser {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(Ed25519Codec)
+    }
+}
+
+struct Ed25519Codec;
+
+impl Multicodec for Ed25519Codec {
+    fn code_u64(&self) -> u64 {
+        0xED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use xdid_core::did::Did;
+
+    use super::*;
+    use crate::parser::DidKeyParser;
+
+    #[test]
+    fn test_display() {
+        let pair = Ed25519KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let did_str = did.to_string();
+        println!("{did_str}");
+        assert!(did_str.starts_with("did:key:z6Mk"));
+    }
+
+    #[test]
+    fn test_jwk() {
+        let pair = Ed25519KeyPair::generate();
+        let jwk = pair.public().to_jwk();
+        assert!(matches!(
+            jwk.key,
+            Key::Okp(Okp {
+                crv: OkpCurves::Ed25519,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        let pair = Ed25519KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let parser = DidKeyParser::default();
+        let _ = parser.parse(&did).expect("parse should succeed");
+    }
+
+    #[test]
+    fn test_parse_spec_vector() {
+        let did = Did::from_str("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp")
+            .expect("valid DID");
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        assert_eq!(key.to_did(), did);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let did = Ed25519KeyPair::generate().public().to_did();
+        let doc = crate::resolve_inner(did.clone()).expect("resolution should succeed");
+
+        let method = &doc.verification_method.expect("verification method")[0];
+        assert_eq!(
+            method.public_key_multibase.as_deref(),
+            Some(did.method_id.0.as_str())
+        );
+        assert!(matches!(
+            method.public_key_jwk.as_ref().map(|jwk| &jwk.key),
+            Some(Key::Okp(Okp {
+                crv: OkpCurves::Ed25519,
+                ..
+            }))
+        ));
+    }
+
+    #[cfg(feature = "x25519")]
+    #[test]
+    fn test_key_agreement_spec_vector() {
+        let did = Did::from_str("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK")
+            .expect("valid DID");
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        let key_agreement = key.to_key_agreement().expect("derived X25519 key");
+        assert_eq!(
+            key_agreement.to_multibase(),
+            "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+        );
+
+        let doc = crate::resolve_inner(did).expect("resolution should succeed");
+        assert_eq!(doc.verification_method.map(|v| v.len()), Some(2));
+        assert_eq!(doc.key_agreement.map(|v| v.len()), Some(1));
+    }
+
+    #[test]
+    fn test_pkcs8_roundtrip() {
+        let pair = Ed25519KeyPair::generate();
+        let pem = pair.to_pkcs8_pem().expect("export should succeed");
+        let imported = Ed25519KeyPair::from_pkcs8_pem(&pem).expect("import should succeed");
+        assert!(pair == imported);
+    }
+
+    #[test]
+    fn test_parse_jwk() {
+        let pair = Ed25519KeyPair::generate();
+        let public = pair.public();
+
+        let parser = DidKeyParser::default();
+        let key = parser
+            .parse_jwk(&public.to_jwk())
+            .expect("parse should succeed");
+        assert_eq!(key.to_did(), public.to_did());
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let pair = Ed25519KeyPair::generate();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign(&msg).expect("signing should succeed");
+
+        pair.public()
+            .verify(&msg, &signature)
+            .expect("verification should succeed");
+        assert!(pair.public().verify(&[9], &signature).is_err());
+    }
+}
@@ -1,4 +1,7 @@
-use jose_jwk::Jwk;
+use jose_jwk::{
+    Jwk,
+    jose_jwa::Signing,
+};
 use multibase::Base;
 use xdid_core::did::{
     Did,
@@ -9,8 +12,12 @@ use zeroize::Zeroizing;
 
 use crate::NAME;
 
+#[cfg(feature = "ed25519")] pub mod ed25519;
 #[cfg(feature = "p256")] pub mod p256;
 #[cfg(feature = "p384")] pub mod p384;
+#[cfg(feature = "p521")] pub mod p521;
+#[cfg(feature = "secp256k1")] pub mod secp256k1;
+#[cfg(feature = "x25519")] pub mod x25519;
 
 pub trait Signer {
     /// Sign a message with the private key.
@@ -19,6 +26,38 @@ pub trait Signer {
     ///
     /// Returns an error if signing fails.
     fn sign(&self, message: &[u8]) -> anyhow::Result<Vec<u8>>;
+
+    /// Sign a message with the private key, producing a fixed-size (IEEE
+    /// P1363) encoded signature, as used by JWS. ECDSA signatures are `r || s`
+    /// rather than DER.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if signing fails.
+    fn sign_fixed(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        self.sign(message)
+    }
+}
+
+pub trait Verifier {
+    /// Verify a signature over a message with the public key.
+    ///
+    /// ECDSA signatures may be either DER or fixed-size (IEEE P1363) encoded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is malformed or invalid.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()>;
+
+    /// Verify a fixed-size (IEEE P1363) encoded signature over a message with
+    /// the public key, as used by JWS. DER encoded signatures are rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is malformed or invalid.
+    fn verify_fixed(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        self.verify(message, signature)
+    }
 }
 
 pub trait DidKeyPair: Signer + Sized {
@@ -42,12 +81,28 @@ pub trait DidKeyPair: Signer + Sized {
     fn from_pkcs8_pem(pem: &str) -> anyhow::Result<Self>;
 }
 
-pub trait PublicKey: WithMulticodec {
+pub trait PublicKey: WithMulticodec + Verifier {
     fn to_sec1_bytes(&self) -> Box<[u8]>;
     fn to_encoded_point_bytes(&self) -> Box<[u8]>;
     fn to_jwk(&self) -> Jwk;
 
-    fn to_did(&self) -> Did {
+    /// JWS algorithm for signatures made with this key, or `None` if the key
+    /// cannot sign.
+    fn jws_alg(&self) -> Option<Signing>;
+
+    /// Whether this key can only be used for key agreement, not for
+    /// signatures.
+    fn is_key_agreement(&self) -> bool {
+        false
+    }
+
+    /// Key agreement key derived from this key, if the key type has one.
+    fn to_key_agreement(&self) -> Option<Box<dyn PublicKey>> {
+        None
+    }
+
+    /// Multicodec-prefixed, base58btc multibase encoding of the key.
+    fn to_multibase(&self) -> String {
         let bytes = self.to_encoded_point_bytes();
         let code = self.codec().code();
 
@@ -55,11 +110,13 @@ pub trait PublicKey: WithMulticodec {
         inner.extend(code);
         inner.extend(bytes);
 
-        let id = multibase::encode(Base::Base58Btc, inner);
+        multibase::encode(Base::Base58Btc, inner)
+    }
 
+    fn to_did(&self) -> Did {
         Did {
             method_name: MethodName(NAME.into()),
-            method_id:   MethodId(id),
+            method_id:   MethodId(self.to_multibase()),
         }
     }
 }
@@ -83,4 +140,24 @@ pub trait KeyParser: WithMulticodec {
     ///
     /// Returns an error if the bytes do not represent a valid public key.
     fn parse(&self, public_key: Vec<u8>) -> Result<Box<dyn PublicKey>, crate::parser::ParseError>;
+
+    /// Parse a public key from a JWK. Returns `None` if the JWK is not of
+    /// this parser's key type.
+    fn parse_jwk(&self, jwk: &Jwk)
+    -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>>;
+}
+
+/// Uncompressed SEC1 encoding of an EC JWK's public point.
+#[cfg(any(
+    feature = "p256",
+    feature = "p384",
+    feature = "p521",
+    feature = "secp256k1"
+))]
+fn ec_point_bytes(ec: &jose_jwk::Ec) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + ec.x.len() + ec.y.len());
+    bytes.push(0x04);
+    bytes.extend_from_slice(&ec.x);
+    bytes.extend_from_slice(&ec.y);
+    bytes
 }
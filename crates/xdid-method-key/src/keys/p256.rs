@@ -1,10 +1,19 @@
-use jose_jwk::Jwk;
+use jose_jwk::{
+    EcCurves,
+    Jwk,
+    Key,
+    jose_jwa::Signing,
+};
 use p256::{
     SecretKey,
     ecdsa::{
         Signature,
         SigningKey,
-        signature::SignerMut,
+        VerifyingKey,
+        signature::{
+            SignerMut,
+            Verifier as _,
+        },
     },
     elliptic_curve::{
         rand_core::OsRng,
@@ -27,6 +36,7 @@ use super::{
     Multicodec,
     PublicKey,
     Signer,
+    Verifier,
     WithMulticodec,
 };
 
@@ -60,6 +70,12 @@ impl Signer for P256KeyPair {
         let sig: Signature = signing_key.sign(message);
         Ok(sig.to_der().as_bytes().to_vec())
     }
+
+    fn sign_fixed(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut signing_key = SigningKey::from(&self.0);
+        let sig: Signature = signing_key.sign(message);
+        Ok(sig.to_bytes().to_vec())
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -77,6 +93,26 @@ impl PublicKey for P256PublicKey {
         let jwk_str = self.0.to_jwk_string();
         serde_json::from_str(&jwk_str).expect("p256 crate guarantees valid JWK")
     }
+
+    fn jws_alg(&self) -> Option<Signing> {
+        Some(Signing::Es256)
+    }
+}
+
+impl Verifier for P256PublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_der(signature).or_else(|_| Signature::from_slice(signature))?;
+        let verifying_key = VerifyingKey::from(&self.0);
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
+
+    fn verify_fixed(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_slice(signature)?;
+        let verifying_key = VerifyingKey::from(&self.0);
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
 }
 
 impl WithMulticodec for P256PublicKey {
@@ -96,6 +132,16 @@ impl KeyParser for P256KeyParser {
             .ok_or(crate::parser::ParseError::InvalidPublicKey)?;
         Ok(Box::new(P256PublicKey(key)))
     }
+
+    fn parse_jwk(
+        &self,
+        jwk: &Jwk,
+    ) -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>> {
+        match &jwk.key {
+            Key::Ec(ec) if ec.crv == EcCurves::P256 => Some(self.parse(super::ec_point_bytes(ec))),
+            _ => None,
+        }
+    }
 }
 
 impl WithMulticodec for P256KeyParser {
@@ -114,12 +160,6 @@ impl Multicodec for P256Codec {
 
 #[cfg(test)]
 mod tests {
-    use p256::ecdsa::{
-        Signature as EcdsaSignature,
-        VerifyingKey,
-        signature::Verifier,
-    };
-
     use super::*;
     use crate::parser::DidKeyParser;
 
@@ -148,6 +188,18 @@ mod tests {
         let _ = parser.parse(&did).expect("parse should succeed");
     }
 
+    #[test]
+    fn test_parse_jwk() {
+        let pair = P256KeyPair::generate();
+        let public = pair.public();
+
+        let parser = DidKeyParser::default();
+        let key = parser
+            .parse_jwk(&public.to_jwk())
+            .expect("parse should succeed");
+        assert_eq!(key.to_did(), public.to_did());
+    }
+
     #[test]
     fn test_sign_verify() {
         let pair = P256KeyPair::generate();
@@ -155,10 +207,43 @@ mod tests {
         let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
         let signature = pair.sign(&msg).expect("signing should succeed");
 
-        let verifying_key = VerifyingKey::from(pair.0.public_key());
-        let sig = EcdsaSignature::from_der(&signature).expect("valid signature");
-        verifying_key
-            .verify(&msg, &sig)
+        pair.public()
+            .verify(&msg, &signature)
+            .expect("verification should succeed");
+        assert!(pair.public().verify(&[9], &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_fixed_size() {
+        let pair = P256KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign(&msg).expect("signing should succeed");
+        let fixed = Signature::from_der(&signature)
+            .expect("valid signature")
+            .to_bytes();
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        key.verify(&msg, &fixed)
+            .expect("verification should succeed");
+
+        key.verify_fixed(&msg, &fixed)
+            .expect("verification should succeed");
+        assert!(key.verify_fixed(&msg, &signature).is_err());
+    }
+
+    #[test]
+    fn test_sign_fixed() {
+        let pair = P256KeyPair::generate();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign_fixed(&msg).expect("signing should succeed");
+        assert_eq!(signature.len(), 64);
+
+        pair.public()
+            .verify_fixed(&msg, &signature)
             .expect("verification should succeed");
     }
 }
@@ -1,4 +1,9 @@
-use jose_jwk::Jwk;
+use jose_jwk::{
+    EcCurves,
+    Jwk,
+    Key,
+    jose_jwa::Signing,
+};
 use p256::{
     elliptic_curve::{
         rand_core::OsRng,
@@ -14,7 +19,11 @@ use p384::{
     ecdsa::{
         Signature,
         SigningKey,
-        signature::SignerMut,
+        VerifyingKey,
+        signature::{
+            SignerMut,
+            Verifier as _,
+        },
     },
     elliptic_curve::sec1::{
         FromEncodedPoint,
@@ -29,6 +38,7 @@ use super::{
     Multicodec,
     PublicKey,
     Signer,
+    Verifier,
     WithMulticodec,
 };
 
@@ -62,6 +72,12 @@ impl Signer for P384KeyPair {
         let sig: Signature = signing_key.sign(message);
         Ok(sig.to_der().as_bytes().to_vec())
     }
+
+    fn sign_fixed(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut signing_key = SigningKey::from(&self.0);
+        let sig: Signature = signing_key.sign(message);
+        Ok(sig.to_bytes().to_vec())
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -79,6 +95,26 @@ impl PublicKey for P384PublicKey {
         let jwk_str = self.0.to_jwk_string();
         serde_json::from_str(&jwk_str).expect("p384 crate guarantees valid JWK")
     }
+
+    fn jws_alg(&self) -> Option<Signing> {
+        Some(Signing::Es384)
+    }
+}
+
+impl Verifier for P384PublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_der(signature).or_else(|_| Signature::from_slice(signature))?;
+        let verifying_key = VerifyingKey::from(&self.0);
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
+
+    fn verify_fixed(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_slice(signature)?;
+        let verifying_key = VerifyingKey::from(&self.0);
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
 }
 
 impl WithMulticodec for P384PublicKey {
@@ -98,6 +134,16 @@ impl KeyParser for P384KeyParser {
             .ok_or(crate::parser::ParseError::InvalidPublicKey)?;
         Ok(Box::new(P384PublicKey(key)))
     }
+
+    fn parse_jwk(
+        &self,
+        jwk: &Jwk,
+    ) -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>> {
+        match &jwk.key {
+            Key::Ec(ec) if ec.crv == EcCurves::P384 => Some(self.parse(super::ec_point_bytes(ec))),
+            _ => None,
+        }
+    }
 }
 
 impl WithMulticodec for P384KeyParser {
@@ -116,12 +162,6 @@ impl Multicodec for P384Codec {
 
 #[cfg(test)]
 mod tests {
-    use p384::ecdsa::{
-        Signature as EcdsaSignature,
-        VerifyingKey,
-        signature::Verifier,
-    };
-
     use super::*;
     use crate::parser::DidKeyParser;
 
@@ -150,6 +190,18 @@ mod tests {
         let _ = parser.parse(&did).expect("parse should succeed");
     }
 
+    #[test]
+    fn test_parse_jwk() {
+        let pair = P384KeyPair::generate();
+        let public = pair.public();
+
+        let parser = DidKeyParser::default();
+        let key = parser
+            .parse_jwk(&public.to_jwk())
+            .expect("parse should succeed");
+        assert_eq!(key.to_did(), public.to_did());
+    }
+
     #[test]
     fn test_sign_verify() {
         let pair = P384KeyPair::generate();
@@ -157,10 +209,39 @@ mod tests {
         let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
         let signature = pair.sign(&msg).expect("signing should succeed");
 
-        let verifying_key = VerifyingKey::from(pair.0.public_key());
-        let sig = EcdsaSignature::from_der(&signature).expect("valid signature");
-        verifying_key
-            .verify(&msg, &sig)
+        pair.public()
+            .verify(&msg, &signature)
+            .expect("verification should succeed");
+        assert!(pair.public().verify(&[9], &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_fixed_size() {
+        let pair = P384KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign(&msg).expect("signing should succeed");
+        let fixed = Signature::from_der(&signature)
+            .expect("valid signature")
+            .to_bytes();
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        key.verify(&msg, &fixed)
+            .expect("verification should succeed");
+    }
+
+    #[test]
+    fn test_sign_fixed() {
+        let pair = P384KeyPair::generate();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign_fixed(&msg).expect("signing should succeed");
+        assert_eq!(signature.len(), 96);
+
+        pair.public()
+            .verify_fixed(&msg, &signature)
             .expect("verification should succeed");
     }
 }
@@ -0,0 +1,260 @@
+use jose_jwk::{
+    EcCurves,
+    Jwk,
+    Key,
+    jose_jwa::Signing,
+};
+use p521::{
+    SecretKey,
+    ecdsa::{
+        Signature,
+        SigningKey,
+        VerifyingKey,
+        signature::{
+            SignerMut,
+            Verifier as _,
+        },
+    },
+    elliptic_curve::{
+        rand_core::OsRng,
+        sec1::{
+            FromEncodedPoint,
+            ToEncodedPoint,
+        },
+        zeroize::Zeroizing,
+    },
+    pkcs8::{
+        DecodePrivateKey,
+        EncodePrivateKey,
+        LineEnding,
+    },
+};
+
+use super::{
+    DidKeyPair,
+    KeyParser,
+    Multicodec,
+    PublicKey,
+    Signer,
+    Verifier,
+    WithMulticodec,
+};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct P521KeyPair(SecretKey);
+
+impl DidKeyPair for P521KeyPair {
+    fn generate() -> Self {
+        let mut rng = OsRng;
+        let secret = SecretKey::random(&mut rng);
+        Self(secret)
+    }
+
+    fn public(&self) -> impl PublicKey {
+        P521PublicKey(self.0.public_key())
+    }
+
+    fn to_pkcs8_pem(&self) -> anyhow::Result<Zeroizing<String>> {
+        let pem = self.0.to_pkcs8_pem(LineEnding::LF)?;
+        Ok(pem)
+    }
+    fn from_pkcs8_pem(pem: &str) -> anyhow::Result<Self> {
+        let key = SecretKey::from_pkcs8_pem(pem)?;
+        Ok(Self(key))
+    }
+}
+
+impl Signer for P521KeyPair {
+    fn sign(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut signing_key = SigningKey::from_bytes(&self.0.to_bytes())?;
+        let sig: Signature = signing_key.sign(message);
+        Ok(sig.to_der().as_bytes().to_vec())
+    }
+
+    fn sign_fixed(&self, message: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut signing_key = SigningKey::from_bytes(&self.0.to_bytes())?;
+        let sig: Signature = signing_key.sign(message);
+        Ok(sig.to_bytes().to_vec())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct P521PublicKey(p521::PublicKey);
+
+impl PublicKey for P521PublicKey {
+    fn to_sec1_bytes(&self) -> Box<[u8]> {
+        self.0.to_sec1_bytes()
+    }
+    fn to_encoded_point_bytes(&self) -> Box<[u8]> {
+        self.0.to_encoded_point(true).as_bytes().into()
+    }
+
+    fn to_jwk(&self) -> Jwk {
+        let jwk_str = self.0.to_jwk_string();
+        serde_json::from_str(&jwk_str).expect("p521 crate guarantees valid JWK")
+    }
+
+    fn jws_alg(&self) -> Option<Signing> {
+        Some(Signing::Es512)
+    }
+}
+
+impl Verifier for P521PublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_der(signature).or_else(|_| Signature::from_slice(signature))?;
+        let verifying_key = VerifyingKey::from_affine(*self.0.as_affine())?;
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
+
+    fn verify_fixed(&self, message: &[u8], signature: &[u8]) -> anyhow::Result<()> {
+        let sig = Signature::from_slice(signature)?;
+        let verifying_key = VerifyingKey::from_affine(*self.0.as_affine())?;
+        verifying_key.verify(message, &sig)?;
+        Ok(())
+    }
+}
+
+impl WithMulticodec for P521PublicKey {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(P521Codec)
+    }
+}
+
+pub(crate) struct P521KeyParser;
+
+impl KeyParser for P521KeyParser {
+    fn parse(&self, public_key: Vec<u8>) -> Result<Box<dyn PublicKey>, crate::parser::ParseError> {
+        let point = p521::EncodedPoint::from_bytes(public_key)
+            .map_err(|_| crate::parser::ParseError::InvalidPublicKey)?;
+        let key = p521::PublicKey::from_encoded_point(&point)
+            .into_option()
+            .ok_or(crate::parser::ParseError::InvalidPublicKey)?;
+        Ok(Box::new(P521PublicKey(key)))
+    }
+
+    fn parse_jwk(
+        &self,
+        jwk: &Jwk,
+    ) -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>> {
+        match &jwk.key {
+            Key::Ec(ec) if ec.crv == EcCurves::P521 => Some(self.parse(super::ec_point_bytes(ec))),
+            _ => None,
+        }
+    }
+}
+
+impl WithMulticodec for P521KeyParser {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(P521Codec)
+    }
+}
+
+struct P521Codec;
+
+impl Multicodec for P521Codec {
+    fn code_u64(&self) -> u64 {
+        0x1202
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::DidKeyParser;
+
+    #[test]
+    fn test_display() {
+        let pair = P521KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let did_str = did.to_string();
+        println!("{did_str}");
+        assert!(did_str.starts_with("did:key:z2J9"));
+    }
+
+    #[test]
+    fn test_jwk() {
+        let pair = P521KeyPair::generate();
+        let jwk = pair.public().to_jwk();
+        assert!(matches!(
+            jwk.key,
+            Key::Ec(jose_jwk::Ec {
+                crv: EcCurves::P521,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse() {
+        let pair = P521KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let parser = DidKeyParser::default();
+        let _ = parser.parse(&did).expect("parse should succeed");
+    }
+
+    #[test]
+    fn test_pkcs8_roundtrip() {
+        let pair = P521KeyPair::generate();
+        let pem = pair.to_pkcs8_pem().expect("export should succeed");
+        let imported = P521KeyPair::from_pkcs8_pem(&pem).expect("import should succeed");
+        assert!(pair == imported);
+    }
+
+    #[test]
+    fn test_parse_jwk() {
+        let pair = P521KeyPair::generate();
+        let public = pair.public();
+
+        let parser = DidKeyParser::default();
+        let key = parser
+            .parse_jwk(&public.to_jwk())
+            .expect("parse should succeed");
+        assert_eq!(key.to_did(), public.to_did());
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let pair = P521KeyPair::generate();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign(&msg).expect("signing should succeed");
+
+        pair.public()
+            .verify(&msg, &signature)
+            .expect("verification should succeed");
+        assert!(pair.public().verify(&[9], &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_fixed_size() {
+        let pair = P521KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign(&msg).expect("signing should succeed");
+        let fixed = Signature::from_der(&signature)
+            .expect("valid signature")
+            .to_bytes();
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        key.verify(&msg, &fixed)
+            .expect("verification should succeed");
+    }
+
+    #[test]
+    fn test_sign_fixed() {
+        let pair = P521KeyPair::generate();
+
+        let msg = vec![0, 1, 2, 3, 4, 5, 6, 7, 8];
+        let signature = pair.sign_fixed(&msg).expect("signing should succeed");
+        assert_eq!(signature.len(), 132);
+
+        pair.public()
+            .verify_fixed(&msg, &signature)
+            .expect("verification should succeed");
+    }
+}
@@ -0,0 +1,142 @@
+use jose_jwk::{
+    Jwk,
+    Key,
+    Okp,
+    OkpCurves,
+    Parameters,
+    jose_jwa::Signing,
+};
+
+use super::{
+    KeyParser,
+    Multicodec,
+    PublicKey,
+    Verifier,
+    WithMulticodec,
+};
+
+/// X25519 public key, used only for key agreement.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct X25519PublicKey(pub(crate) [u8; 32]);
+
+impl PublicKey for X25519PublicKey {
+    fn to_sec1_bytes(&self) -> Box<[u8]> {
+        self.0.as_slice().into()
+    }
+    fn to_encoded_point_bytes(&self) -> Box<[u8]> {
+        self.0.as_slice().into()
+    }
+
+    fn to_jwk(&self) -> Jwk {
+        Jwk {
+            key: Key::Okp(Okp {
+                crv: OkpCurves::X25519,
+                x:   self.0.to_vec().into(),
+                d:   None,
+            }),
+            prm: Parameters::default(),
+        }
+    }
+
+    fn jws_alg(&self) -> Option<Signing> {
+        None
+    }
+
+    fn is_key_agreement(&self) -> bool {
+        true
+    }
+}
+
+impl Verifier for X25519PublicKey {
+    fn verify(&self, _message: &[u8], _signature: &[u8]) -> anyhow::Result<()> {
+        anyhow::bail!("X25519 keys cannot verify signatures")
+    }
+}
+
+impl WithMulticodec for X25519PublicKey {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(X25519Codec)
+    }
+}
+
+pub(crate) struct X25519KeyParser;
+
+impl KeyParser for X25519KeyParser {
+    fn parse(&self, public_key: Vec<u8>) -> Result<Box<dyn PublicKey>, crate::parser::ParseError> {
+        let bytes: [u8; 32] = public_key
+            .try_into()
+            .map_err(|_| crate::parser::ParseError::InvalidPublicKey)?;
+        Ok(Box::new(X25519PublicKey(bytes)))
+    }
+
+    fn parse_jwk(
+        &self,
+        jwk: &Jwk,
+    ) -> Option<Result<Box<dyn PublicKey>, crate::parser::ParseError>> {
+        match &jwk.key {
+            Key::Okp(okp) if okp.crv == OkpCurves::X25519 => Some(self.parse(okp.x.to_vec())),
+            _ => None,
+        }
+    }
+}
+
+impl WithMulticodec for X25519KeyParser {
+    fn codec(&self) -> Box<dyn Multicodec> {
+        Box::new(X25519Codec)
+    }
+}
+
+struct X25519Codec;
+
+impl Multicodec for X25519Codec {
+    fn code_u64(&self) -> u64 {
+        0xEC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use xdid_core::did::Did;
+
+    use super::*;
+    use crate::parser::DidKeyParser;
+
+    const DID: &str = "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F";
+
+    #[test]
+    fn test_parse_spec_vector() {
+        let did = Did::from_str(DID).expect("valid DID");
+
+        let parser = DidKeyParser::default();
+        let key = parser.parse(&did).expect("parse should succeed");
+        assert!(key.is_key_agreement());
+        assert_eq!(key.to_did(), did);
+    }
+
+    #[test]
+    fn test_jwk() {
+        let key = X25519PublicKey([7; 32]);
+        let jwk = key.to_jwk();
+        assert!(matches!(
+            jwk.key,
+            Key::Okp(Okp {
+                crv: OkpCurves::X25519,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let did = Did::from_str(DID).expect("valid DID");
+        let doc = crate::resolve_inner(did).expect("resolution should succeed");
+
+        assert_eq!(doc.key_agreement.map(|v| v.len()), Some(1));
+        assert!(doc.authentication.is_none());
+        assert!(doc.assertion_method.is_none());
+        assert!(doc.capability_invocation.is_none());
+        assert!(doc.capability_delegation.is_none());
+    }
+}
@@ -1,6 +1,5 @@
 //! [xdid](https://github.com/unavi-xyz/xdid) implementation of [did:key](https://w3c-ccg.github.io/did-method-key/).
 
-use parser::DidKeyParser;
 use xdid_core::{
     Method,
     MethodFuture,
@@ -18,6 +17,10 @@ mod keys;
 mod parser;
 
 pub use keys::*;
+pub use parser::{
+    DidKeyParser,
+    ParseError,
+};
 
 const NAME: &str = "key";
 
@@ -37,9 +40,7 @@ fn resolve_inner(did: Did) -> Result<Document, ResolutionError> {
     debug_assert_eq!(did.method_name.0, NAME);
 
     let parser = DidKeyParser::default();
-    let did_key = parser
-        .parse(&did)
-        .map_err(|_| ResolutionError::InvalidDid)?;
+    let did_key = parser.parse(&did).map_err(ResolutionError::invalid_did)?;
 
     let did_url = DidUrl {
         did:          did.clone(),
@@ -48,22 +49,56 @@ fn resolve_inner(did: Did) -> Result<Document, ResolutionError> {
         query:        None,
     };
 
+    let mut verification_method = vec![verification_method_map(&did, did_url.clone(), &*did_key)];
+
+    // X25519 keys can only be used for key agreement.
+    if did_key.is_key_agreement() {
+        return Ok(Document {
+            id:                    did,
+            also_known_as:         None,
+            controller:            None,
+            verification_method:   Some(verification_method),
+            authentication:        None,
+            assertion_method:      None,
+            capability_invocation: None,
+            capability_delegation: None,
+            service:               None,
+            key_agreement:         Some(vec![VerificationMethod::Url(did_url)]),
+        });
+    }
+
+    // Ed25519 keys also produce a derived X25519 key agreement method.
+    let key_agreement = did_key.to_key_agreement().map(|key| {
+        let key_url = DidUrl {
+            did:          did.clone(),
+            fragment:     Some(key.to_multibase().into()),
+            path_abempty: None,
+            query:        None,
+        };
+        verification_method.push(verification_method_map(&did, key_url.clone(), &*key));
+        vec![VerificationMethod::Url(key_url)]
+    });
+
     Ok(Document {
-        id:                    did.clone(),
-        also_known_as:         None,
-        controller:            None,
-        verification_method:   Some(vec![VerificationMethodMap {
-            id:                   did_url.clone(),
-            typ:                  "JsonWebKey2020".into(),
-            controller:           did,
-            public_key_jwk:       Some(did_key.to_jwk()),
-            public_key_multibase: None,
-        }]),
-        authentication:        Some(vec![VerificationMethod::Url(did_url.clone())]),
-        assertion_method:      Some(vec![VerificationMethod::Url(did_url.clone())]),
+        id: did,
+        also_known_as: None,
+        controller: None,
+        verification_method: Some(verification_method),
+        authentication: Some(vec![VerificationMethod::Url(did_url.clone())]),
+        assertion_method: Some(vec![VerificationMethod::Url(did_url.clone())]),
         capability_invocation: Some(vec![VerificationMethod::Url(did_url.clone())]),
         capability_delegation: Some(vec![VerificationMethod::Url(did_url)]),
-        service:               None,
-        key_agreement:         None,
+        service: None,
+        key_agreement,
     })
 }
+
+fn verification_method_map(did: &Did, id: DidUrl, key: &dyn PublicKey) -> VerificationMethodMap {
+    VerificationMethodMap {
+        id,
+        typ: "JsonWebKey2020".into(),
+        controller: did.clone(),
+        public_key_jwk: Some(key.to_jwk()),
+        public_key_multibase: Some(key.to_multibase()),
+    }
+}
@@ -1,21 +1,29 @@
+use jose_jwk::Jwk;
 use multibase::Base;
 use smallvec::SmallVec;
 use thiserror::Error;
-use xdid_core::did::Did;
+use xdid_core::{
+    did::Did,
+    document::VerificationMethodMap,
+};
 
 use crate::keys::{
     KeyParser,
     PublicKey,
 };
 
+/// Parses public keys from did:key identifiers and verification methods.
 pub struct DidKeyParser {
-    parsers: SmallVec<[Box<dyn KeyParser>; 2]>,
+    parsers: SmallVec<[Box<dyn KeyParser>; 6]>,
 }
 
 impl Default for DidKeyParser {
     fn default() -> Self {
         #[allow(unused_mut)]
-        let mut parsers = SmallVec::<[Box<dyn KeyParser>; 2]>::new();
+        let mut parsers = SmallVec::<[Box<dyn KeyParser>; 6]>::new();
+
+        #[cfg(feature = "ed25519")]
+        parsers.push(Box::new(crate::keys::ed25519::Ed25519KeyParser));
 
         #[cfg(feature = "p256")]
         parsers.push(Box::new(crate::keys::p256::P256KeyParser));
@@ -23,14 +31,41 @@ impl Default for DidKeyParser {
         #[cfg(feature = "p384")]
         parsers.push(Box::new(crate::keys::p384::P384KeyParser));
 
+        #[cfg(feature = "p521")]
+        parsers.push(Box::new(crate::keys::p521::P521KeyParser));
+
+        #[cfg(feature = "secp256k1")]
+        parsers.push(Box::new(crate::keys::secp256k1::Secp256k1KeyParser));
+
+        #[cfg(feature = "x25519")]
+        parsers.push(Box::new(crate::keys::x25519::X25519KeyParser));
+
         Self { parsers }
     }
 }
 
 impl DidKeyParser {
+    /// Parse the public key of a did:key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key type is not supported or the key is
+    /// invalid.
     pub fn parse(&self, did: &Did) -> Result<Box<dyn PublicKey>, ParseError> {
-        let (base, inner) = multibase::decode(&did.method_id.0)?;
-        debug_assert_eq!(base, Base::Base58Btc);
+        self.parse_multibase(&did.method_id.0)
+    }
+
+    /// Parse a multicodec-prefixed, multibase encoded public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key type is not supported or the key is
+    /// invalid.
+    pub fn parse_multibase(&self, value: &str) -> Result<Box<dyn PublicKey>, ParseError> {
+        let (base, inner) = multibase::decode(value)?;
+        if base != Base::Base58Btc {
+            return Err(ParseError::UnsupportedBase(base));
+        }
 
         for parser in &self.parsers {
             let code = parser.codec().code();
@@ -41,14 +76,56 @@ impl DidKeyParser {
 
         Err(ParseError::CodecNotSupported)
     }
+
+    /// Parse a public key from a JWK.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key type is not supported or the key is
+    /// invalid.
+    pub fn parse_jwk(&self, jwk: &Jwk) -> Result<Box<dyn PublicKey>, ParseError> {
+        for parser in &self.parsers {
+            if let Some(res) = parser.parse_jwk(jwk) {
+                return res;
+            }
+        }
+
+        Err(ParseError::CodecNotSupported)
+    }
+
+    /// Parse the public key of a verification method, from either its
+    /// `publicKeyJwk` or `publicKeyMultibase`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method has no public key, or the key is not
+    /// supported.
+    pub fn parse_verification_method(
+        &self,
+        method: &VerificationMethodMap,
+    ) -> Result<Box<dyn PublicKey>, ParseError> {
+        if let Some(jwk) = &method.public_key_jwk {
+            return self.parse_jwk(jwk);
+        }
+
+        if let Some(value) = &method.public_key_multibase {
+            return self.parse_multibase(value);
+        }
+
+        Err(ParseError::MissingPublicKey)
+    }
 }
 
 #[derive(Error, Debug)]
 pub enum ParseError {
     #[error("failed to decode multibase: {0}")]
     Decode(#[from] multibase::Error),
+    #[error("multibase {0:?} not supported")]
+    UnsupportedBase(Base),
     #[error("codec not supported")]
     CodecNotSupported,
     #[error("invalid public key")]
     InvalidPublicKey,
+    #[error("verification method has no public key")]
+    MissingPublicKey,
 }
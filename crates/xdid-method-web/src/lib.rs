@@ -1,24 +1,60 @@
 //! [xdid](https://github.com/unavi-xyz/xdid) implementation of [did:web](https://w3c-ccg.github.io/did-method-web/).
 
+use std::{
+    pin::pin,
+    time::Duration,
+};
+
+use futures_timer::Delay;
+use futures_util::future::{
+    Either,
+    select,
+};
 pub use reqwest;
 use reqwest::{
     Client,
     ClientBuilder,
+    StatusCode,
+    header::{
+        ACCEPT,
+        CACHE_CONTROL,
+        CONTENT_TYPE,
+    },
 };
+use serde_json::Map;
 use xdid_core::{
     Method,
     MethodFuture,
     ResolutionError,
     did::Did,
     document::Document,
+    resolution::{
+        DocumentMetadata,
+        ResolutionMetadata,
+        ResolutionOptions,
+        ResolutionResult,
+    },
 };
 
 mod parse;
+mod validate;
+
+pub use parse::{
+    did_to_url,
+    url_to_did,
+};
 
 const NAME: &str = "web";
 
+/// Default deadline for a resolution, including retries.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct MethodDidWeb {
-    pub client: Client,
+    pub client:  Client,
+    /// Deadline for a resolution, including retries. Resolutions that take
+    /// longer fail with [`ResolutionError::Timeout`].
+    pub timeout: Option<Duration>,
+    pub retry:   RetryPolicy,
 }
 
 impl MethodDidWeb {
@@ -29,7 +65,46 @@ impl MethodDidWeb {
     /// Returns an error if the HTTP client cannot be constructed.
     pub fn new() -> Result<Self, reqwest::Error> {
         let client = ClientBuilder::new().build()?;
-        Ok(Self { client })
+        Ok(Self::with_client(client))
+    }
+
+    /// Create a new did:web resolver using the provided HTTP client.
+    #[must_use]
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            client,
+            timeout: Some(DEFAULT_TIMEOUT),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Retry policy for transient HTTP errors, such as connection failures or
+/// `503 Service Unavailable` responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries:     u32,
+    /// Delay before the first retry, doubling with each attempt.
+    pub initial_backoff: Duration,
+    pub max_backoff:     Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub const NONE: Self = Self {
+        max_retries:     0,
+        initial_backoff: Duration::ZERO,
+        max_backoff:     Duration::ZERO,
+    };
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries:     2,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff:     Duration::from_secs(2),
+        }
     }
 }
 
@@ -38,37 +113,157 @@ impl Method for MethodDidWeb {
         NAME
     }
 
-    #[cfg(not(target_family = "wasm"))]
     fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
-        Box::pin(resolve_inner(self.client.clone(), did))
+        let fut = self.resolve_with_metadata(did, ResolutionOptions::default());
+        Box::pin(async move { fut.await.map(|result| result.document) })
     }
 
-    #[cfg(target_family = "wasm")]
-    fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
-        Box::pin(send_wrapper::SendWrapper::new(resolve_inner(
-            self.client.clone(),
-            did,
-        )))
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        let fut = resolve_with_retry(self.client.clone(), self.retry, did, options);
+        let timeout = self.timeout;
+
+        boxed(async move {
+            let Some(timeout) = timeout else {
+                return fut.await;
+            };
+
+            match select(pin!(fut), Delay::new(timeout)).await {
+                Either::Left((res, _)) => res,
+                Either::Right(_) => Err(ResolutionError::Timeout),
+            }
+        })
     }
 }
 
-async fn resolve_inner(client: Client, did: Did) -> Result<Document, ResolutionError> {
+#[cfg(not(target_family = "wasm"))]
+fn boxed<T>(fut: impl Future<Output = T> + Send + Sync + 'static) -> MethodFuture<T> {
+    Box::pin(fut)
+}
+
+#[cfg(target_family = "wasm")]
+fn boxed<T>(fut: impl Future<Output = T> + 'static) -> MethodFuture<T> {
+    Box::pin(send_wrapper::SendWrapper::new(fut))
+}
+
+/// A failed resolution attempt.
+enum Failure {
+    /// May succeed if retried.
+    Transient(ResolutionError),
+    Permanent(ResolutionError),
+}
+
+impl From<ResolutionError> for Failure {
+    fn from(value: ResolutionError) -> Self {
+        Self::Permanent(value)
+    }
+}
+
+async fn resolve_with_retry(
+    client: Client,
+    retry: RetryPolicy,
+    did: Did,
+    options: ResolutionOptions,
+) -> Result<ResolutionResult, ResolutionError> {
+    let mut backoff = retry.initial_backoff;
+    let mut retries = 0;
+
+    loop {
+        match resolve_inner(&client, &did, &options).await {
+            Ok(result) => return Ok(result),
+            Err(Failure::Transient(_)) if retries < retry.max_retries => {
+                Delay::new(backoff).await;
+                backoff = (backoff * 2).min(retry.max_backoff);
+                retries += 1;
+            }
+            Err(Failure::Transient(e) | Failure::Permanent(e)) => return Err(e),
+        }
+    }
+}
+
+async fn resolve_inner(
+    client: &Client,
+    did: &Did,
+    options: &ResolutionOptions,
+) -> Result<ResolutionResult, Failure> {
     debug_assert_eq!(did.method_name.0, NAME);
 
-    let url = parse::parse_url(&did);
+    options.reject_versions()?;
 
-    let req = client
-        .get(url)
-        .build()
-        .map_err(|_| ResolutionError::InvalidDid)?;
+    let url = parse::did_to_url(did)
+        .ok_or_else(|| ResolutionError::invalid_did("invalid did:web domain or path"))?;
 
-    let doc = client
-        .execute(req)
-        .await
-        .map_err(|e| ResolutionError::ResolutionFailed(e.to_string()))?
-        .json::<Document>()
+    let mut req = client.get(url);
+    if let Some(accept) = &options.accept {
+        req = req.header(ACCEPT, accept);
+    }
+
+    let req = req.build().map_err(ResolutionError::invalid_did)?;
+
+    let res = client.execute(req).await.map_err(|e| {
+        let transient = e.is_connect() || e.is_timeout();
+        let e = ResolutionError::internal(e);
+        if transient {
+            Failure::Transient(e)
+        } else {
+            Failure::Permanent(e)
+        }
+    })?;
+
+    let status = res.status();
+    if !status.is_success() {
+        if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+            return Err(ResolutionError::not_found(format!("HTTP {status}")).into());
+        }
+
+        let e = ResolutionError::internal(format!("HTTP {status}"));
+        let transient = status.is_server_error()
+            || status == StatusCode::TOO_MANY_REQUESTS
+            || status == StatusCode::REQUEST_TIMEOUT;
+        return Err(if transient {
+            Failure::Transient(e)
+        } else {
+            Failure::Permanent(e)
+        });
+    }
+
+    let header = |name| {
+        res.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(ToString::to_string)
+    };
+
+    let content_type = header(CONTENT_TYPE);
+
+    let mut properties = Map::new();
+    if let Some(cache_control) = header(CACHE_CONTROL) {
+        properties.insert(
+            ResolutionMetadata::CACHE_CONTROL.to_string(),
+            cache_control.into(),
+        );
+    }
+
+    let body = res
+        .bytes()
         .await
-        .map_err(|e| ResolutionError::ResolutionFailed(e.to_string()))?;
+        .map_err(|e| Failure::Transient(ResolutionError::internal(e)))?;
+
+    let document =
+        serde_json::from_slice::<Document>(&body).map_err(ResolutionError::invalid_did_document)?;
+
+    validate::validate_document(did, &document)?;
 
-    Ok(doc)
+    Ok(ResolutionResult {
+        document,
+        resolution_metadata: ResolutionMetadata {
+            content_type,
+            properties,
+            ..Default::default()
+        },
+        document_metadata: DocumentMetadata::default(),
+    })
 }
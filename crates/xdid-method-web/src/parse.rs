@@ -1,75 +1,252 @@
+use std::{
+    borrow::Cow,
+    fmt::Write as _,
+    str::FromStr,
+};
+
+use percent_encoding::{
+    AsciiSet,
+    NON_ALPHANUMERIC,
+    percent_decode_str,
+    utf8_percent_encode,
+};
+use url::{
+    Host,
+    Url,
+};
 use xdid_core::did::Did;
 
-pub fn parse_url(did: &Did) -> String {
+/// Characters percent-encoded in a DID segment, leaving `idchar`s.
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'.').remove(b'-').remove(b'_');
+
+/// Transforms a did:web DID into the HTTPS URL of its document.
+///
+/// Segments are percent-decoded, and internationalized domain names are
+/// converted to punycode. Returns `None` if the host is an IP address or
+/// otherwise invalid, or a path segment is empty or contains `/`.
+#[must_use]
+pub fn did_to_url(did: &Did) -> Option<Url> {
     // web-did = "did:web:" domain-name
     // web-did = "did:web:" domain-name * (":" path)
     let mut split = did.method_id.0.split(':');
 
-    let domain = split
-        .next()
-        .expect("method_id validated by Did::from_str")
-        .replace("%3A", ":");
+    let domain = decode(split.next()?)?;
+    if domain.is_empty()
+        || domain
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | '?' | '#' | '@') || c.is_whitespace())
+    {
+        return None;
+    }
 
     // Don't use HTTPS for localhost to make testing easier.
-    let mut url = if domain.starts_with("localhost:") {
-        "http://".to_string()
+    let scheme = if domain == "localhost" || domain.starts_with("localhost:") {
+        "http"
     } else {
-        "https://".to_string()
+        "https"
     };
 
-    url.push_str(&domain);
+    let mut url = Url::parse(&format!("{scheme}://{domain}")).ok()?;
+    if !matches!(url.host(), Some(Host::Domain(_))) {
+        return None;
+    }
+
+    {
+        let mut segments = url.path_segments_mut().ok()?;
+        segments.clear();
+
+        let mut has_path = false;
+
+        for segment in split {
+            has_path = true;
 
-    let mut has_path = false;
+            let segment = decode(segment)?;
+            if segment.is_empty() || segment.contains('/') || segment == "." || segment == ".." {
+                return None;
+            }
+            segments.push(&segment);
+        }
 
-    for path in split {
-        has_path = true;
+        if !has_path {
+            segments.push(".well-known");
+        }
 
-        url.push('/');
-        url.push_str(path);
+        segments.push("did.json");
     }
 
-    if !has_path {
-        url.push_str("/.well-known");
+    Some(url)
+}
+
+/// Transforms the URL of a did:web document back into its DID. Returns
+/// `None` if the URL is not a did:web document URL.
+#[must_use]
+pub fn url_to_did(url: &Url) -> Option<Did> {
+    let Some(Host::Domain(domain)) = url.host() else {
+        return None;
+    };
+
+    let is_localhost = domain == "localhost";
+    let scheme_valid = url.scheme() == "https" || (is_localhost && url.scheme() == "http");
+    if !scheme_valid
+        || !url.username().is_empty()
+        || url.password().is_some()
+        || url.query().is_some()
+        || url.fragment().is_some()
+    {
+        return None;
     }
 
-    url.push_str("/did.json");
+    let mut segments = url.path_segments()?.collect::<Vec<_>>();
+    if segments.pop() != Some("did.json") || segments.is_empty() {
+        return None;
+    }
+    if segments == [".well-known"] {
+        segments.clear();
+    }
+
+    let mut id = domain.to_string();
+    if let Some(port) = url.port() {
+        let _ = write!(id, "%3A{port}");
+    }
+
+    for segment in segments {
+        let segment = decode(segment)?;
+        if segment.is_empty() {
+            return None;
+        }
+
+        id.push(':');
+        id.extend(utf8_percent_encode(&segment, SEGMENT));
+    }
+
+    Did::from_str(&format!("did:web:{id}")).ok()
+}
 
-    url
+fn decode(value: &str) -> Option<String> {
+    percent_decode_str(value)
+        .decode_utf8()
+        .ok()
+        .map(Cow::into_owned)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use super::*;
 
-    use xdid_core::did::Did;
+    fn url(did: &str) -> Option<String> {
+        let did = Did::from_str(did).expect("valid DID");
+        did_to_url(&did).map(String::from)
+    }
 
-    use super::*;
+    fn did(url: &str) -> Option<String> {
+        let url = Url::parse(url).expect("valid URL");
+        url_to_did(&url).map(|did| did.to_string())
+    }
 
     #[test]
     fn test_parse_no_path() {
-        let did = Did::from_str("did:web:w3c-ccg.github.io").expect("valid DID");
-        let url = parse_url(&did);
-        assert_eq!(url, "https://w3c-ccg.github.io/.well-known/did.json");
+        assert_eq!(
+            url("did:web:w3c-ccg.github.io").as_deref(),
+            Some("https://w3c-ccg.github.io/.well-known/did.json")
+        );
     }
 
     #[test]
     fn test_parse_path() {
-        let did = Did::from_str("did:web:w3c-ccg.github.io:user:alice").expect("valid DID");
-        let url = parse_url(&did);
-        assert_eq!(url, "https://w3c-ccg.github.io/user/alice/did.json");
+        assert_eq!(
+            url("did:web:w3c-ccg.github.io:user:alice").as_deref(),
+            Some("https://w3c-ccg.github.io/user/alice/did.json")
+        );
     }
 
     #[test]
     fn test_parse_port() {
-        let did = Did::from_str("did:web:example.com%3A3000:user:alice").expect("valid DID");
-        let url = parse_url(&did);
-        assert_eq!(url, "https://example.com:3000/user/alice/did.json");
+        assert_eq!(
+            url("did:web:example.com%3A3000:user:alice").as_deref(),
+            Some("https://example.com:3000/user/alice/did.json")
+        );
     }
 
     #[test]
     fn test_parse_localhost_http() {
-        let did = Did::from_str("did:web:localhost%3A3000").expect("valid DID");
-        let url = parse_url(&did);
-        assert_eq!(url, "http://localhost:3000/.well-known/did.json");
+        assert_eq!(
+            url("did:web:localhost%3A3000").as_deref(),
+            Some("http://localhost:3000/.well-known/did.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_percent_encoded_path() {
+        assert_eq!(
+            url("did:web:example.com:user%20name:caf%C3%A9").as_deref(),
+            Some("https://example.com/user%20name/caf%C3%A9/did.json")
+        );
+        assert_eq!(url("did:web:example.com:a%2Fb"), None);
+        assert_eq!(url("did:web:example.com:%2E%2E"), None);
+    }
+
+    #[test]
+    fn test_parse_idn() {
+        assert_eq!(
+            url("did:web:m%C3%BCnchen.example").as_deref(),
+            Some("https://xn--mnchen-3ya.example/.well-known/did.json")
+        );
+        assert_eq!(
+            url("did:web:xn--mnchen-3ya.example").as_deref(),
+            Some("https://xn--mnchen-3ya.example/.well-known/did.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_host() {
+        assert_eq!(url("did:web:127.0.0.1"), None);
+        assert_eq!(url("did:web:127.0.0.1%3A8080"), None);
+        assert_eq!(url("did:web:%5B%3A%3A1%5D"), None);
+        assert_eq!(url("did:web:user%40example.com"), None);
+        assert_eq!(url("did:web:example.com%2Fpath"), None);
+        assert_eq!(url("did:web:exa%20mple.com"), None);
+    }
+
+    #[test]
+    fn test_url_to_did() {
+        assert_eq!(
+            did("https://w3c-ccg.github.io/.well-known/did.json").as_deref(),
+            Some("did:web:w3c-ccg.github.io")
+        );
+        assert_eq!(
+            did("https://example.com:3000/user/alice/did.json").as_deref(),
+            Some("did:web:example.com%3A3000:user:alice")
+        );
+        assert_eq!(
+            did("https://münchen.example/user%20name/did.json").as_deref(),
+            Some("did:web:xn--mnchen-3ya.example:user%20name")
+        );
+        assert_eq!(
+            did("http://localhost:3000/.well-known/did.json").as_deref(),
+            Some("did:web:localhost%3A3000")
+        );
+    }
+
+    #[test]
+    fn test_url_to_did_invalid() {
+        assert_eq!(did("https://example.com/user/alice"), None);
+        assert_eq!(did("https://127.0.0.1/.well-known/did.json"), None);
+        assert_eq!(did("http://example.com/.well-known/did.json"), None);
+        assert_eq!(did("https://example.com/did.json?a=1"), None);
+        assert_eq!(did("https://example.com//did.json"), None);
+        assert_eq!(did("https://example.com/did.json"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for id in [
+            "did:web:example.com",
+            "did:web:example.com%3A8443:users:alice",
+            "did:web:xn--mnchen-3ya.example:caf%C3%A9",
+        ] {
+            let did = Did::from_str(id).expect("valid DID");
+            let url = did_to_url(&did).expect("DID should transform");
+            assert_eq!(url_to_did(&url), Some(did));
+        }
     }
 }
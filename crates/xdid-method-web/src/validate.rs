@@ -0,0 +1,186 @@
+use xdid_core::{
+    ResolutionError,
+    did::Did,
+    document::{
+        Document,
+        VerificationMethod,
+        VerificationMethodMap,
+    },
+};
+
+/// Checks that a fetched document belongs to the requested DID, so a host
+/// cannot serve the document of another identity.
+pub fn validate_document(did: &Did, document: &Document) -> Result<(), ResolutionError> {
+    if document.id != *did {
+        return Err(invalid(format!(
+            "document id {} does not match {did}",
+            document.id
+        )));
+    }
+
+    let relationships = [
+        &document.authentication,
+        &document.assertion_method,
+        &document.key_agreement,
+        &document.capability_invocation,
+        &document.capability_delegation,
+    ]
+    .into_iter()
+    .flatten()
+    .flatten();
+
+    for method in document.verification_method.iter().flatten() {
+        validate_method(did, document, method)?;
+    }
+
+    for method in relationships {
+        match method {
+            VerificationMethod::Map(map) => validate_method(did, document, map)?,
+            VerificationMethod::RelativeUrl(url) => {
+                if document.resolve_verification_method(method).is_none() {
+                    return Err(invalid(format!(
+                        "relative reference {url} does not match a verification method"
+                    )));
+                }
+            }
+            VerificationMethod::Url(url) => {
+                // References to other DIDs are checked when they are resolved.
+                if url.did == *did && document.resolve_verification_method(method).is_none() {
+                    return Err(invalid(format!(
+                        "reference {url} does not match a verification method"
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a verification method is identified under the DID, and
+/// controlled by the DID or one of the document's controllers.
+fn validate_method(
+    did: &Did,
+    document: &Document,
+    method: &VerificationMethodMap,
+) -> Result<(), ResolutionError> {
+    if method.id.did != *did {
+        return Err(invalid(format!(
+            "verification method {} is not under {did}",
+            method.id
+        )));
+    }
+
+    let is_controller = method.controller == *did
+        || document
+            .controller
+            .iter()
+            .flatten()
+            .any(|controller| *controller == method.controller);
+    if !is_controller {
+        return Err(invalid(format!(
+            "verification method {} has unauthorized controller {}",
+            method.id, method.controller
+        )));
+    }
+
+    Ok(())
+}
+
+fn invalid(message: String) -> ResolutionError {
+    ResolutionError::invalid_did_document(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    const DID: &str = "did:web:example.com";
+
+    fn document(value: serde_json::Value) -> Document {
+        serde_json::from_value(value).expect("valid document")
+    }
+
+    fn validate(value: serde_json::Value) -> Result<(), ResolutionError> {
+        let did = Did::from_str(DID).expect("valid DID");
+        validate_document(&did, &document(value))
+    }
+
+    fn method(id: &str, controller: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "type": "Multikey",
+            "controller": controller,
+            "publicKeyMultibase": "z6Mk",
+        })
+    }
+
+    #[test]
+    fn test_valid() {
+        validate(json!({
+            "id": DID,
+            "controller": ["did:web:admin.example.com"],
+            "verificationMethod": [
+                method("did:web:example.com#key-1", DID),
+                method("did:web:example.com#key-2", "did:web:admin.example.com"),
+            ],
+            "authentication": [
+                "#key-1",
+                "did:web:example.com#key-2",
+                "did:web:other.com#key-1",
+                method("did:web:example.com#key-3", DID),
+            ],
+        }))
+        .expect("document should be valid");
+    }
+
+    #[test]
+    fn test_id_mismatch() {
+        let res = validate(json!({ "id": "did:web:other.com" }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+
+    #[test]
+    fn test_method_id_mismatch() {
+        let res = validate(json!({
+            "id": DID,
+            "verificationMethod": [method("did:web:other.com#key-1", DID)],
+        }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+
+        let res = validate(json!({
+            "id": DID,
+            "assertionMethod": [method("did:web:other.com#key-1", DID)],
+        }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+
+    #[test]
+    fn test_controller_mismatch() {
+        let res = validate(json!({
+            "id": DID,
+            "verificationMethod": [method("did:web:example.com#key-1", "did:web:other.com")],
+        }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+
+    #[test]
+    fn test_dangling_reference() {
+        let res = validate(json!({
+            "id": DID,
+            "verificationMethod": [method("did:web:example.com#key-1", DID)],
+            "authentication": ["#key-2"],
+        }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+
+        let res = validate(json!({
+            "id": DID,
+            "authentication": ["did:web:example.com#key-1"],
+        }));
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+}
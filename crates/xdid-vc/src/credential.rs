@@ -0,0 +1,240 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::{
+    OneOrMany,
+    serde_as,
+    skip_serializing_none,
+};
+use time::OffsetDateTime;
+use xdid_core::did::Did;
+
+use crate::{
+    CONTEXT_V2,
+    Context,
+    TypedObject,
+    ValidationError,
+    Version,
+};
+
+pub const CREDENTIAL_TYPE: &str = "VerifiableCredential";
+
+/// A [verifiable credential](https://www.w3.org/TR/vc-data-model-2.0/#credentials).
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context")]
+    pub context:            Vec<Context>,
+    pub id:                 Option<String>,
+    #[serde(rename = "type")]
+    #[serde_as(as = "OneOrMany<_>")]
+    pub typ:                Vec<String>,
+    pub name:               Option<String>,
+    pub description:        Option<String>,
+    pub issuer:             Issuer,
+    #[serde_as(as = "OneOrMany<_>")]
+    pub credential_subject: Vec<CredentialSubject>,
+    /// Start of the validity period (2.0).
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub valid_from:         Option<OffsetDateTime>,
+    /// End of the validity period (2.0).
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub valid_until:        Option<OffsetDateTime>,
+    /// Start of the validity period (1.1).
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub issuance_date:      Option<OffsetDateTime>,
+    /// End of the validity period (1.1).
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expiration_date:    Option<OffsetDateTime>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub credential_status:  Option<Vec<TypedObject>>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub credential_schema:  Option<Vec<TypedObject>>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub refresh_service:    Option<Vec<TypedObject>>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub terms_of_use:       Option<Vec<TypedObject>>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub evidence:           Option<Vec<TypedObject>>,
+    /// Additional properties, such as an embedded `proof`.
+    #[serde(flatten)]
+    pub properties:         Map<String, Value>,
+}
+
+impl VerifiableCredential {
+    /// Creates a VC Data Model 2.0 credential.
+    #[must_use]
+    pub fn new(issuer: Did, subject: CredentialSubject) -> Self {
+        Self {
+            context:            vec![Context::Url(CONTEXT_V2.into())],
+            id:                 None,
+            typ:                vec![CREDENTIAL_TYPE.into()],
+            name:               None,
+            description:        None,
+            issuer:             Issuer::from(issuer),
+            credential_subject: vec![subject],
+            valid_from:         None,
+            valid_until:        None,
+            issuance_date:      None,
+            expiration_date:    None,
+            credential_status:  None,
+            credential_schema:  None,
+            refresh_service:    None,
+            terms_of_use:       None,
+            evidence:           None,
+            properties:         Map::new(),
+        }
+    }
+
+    /// Data model version, determined by the base `@context`.
+    #[must_use]
+    pub fn version(&self) -> Option<Version> {
+        Context::version(&self.context)
+    }
+
+    /// Start of the validity period, regardless of data model version.
+    #[must_use]
+    pub const fn valid_from(&self) -> Option<OffsetDateTime> {
+        match self.valid_from {
+            Some(v) => Some(v),
+            None => self.issuance_date,
+        }
+    }
+
+    /// End of the validity period, regardless of data model version.
+    #[must_use]
+    pub const fn valid_until(&self) -> Option<OffsetDateTime> {
+        match self.valid_until {
+            Some(v) => Some(v),
+            None => self.expiration_date,
+        }
+    }
+
+    /// Checks that the credential is structurally valid for its data model
+    /// version.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the credential violates.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let version = self.version().ok_or(ValidationError::InvalidContext)?;
+
+        if !self.typ.iter().any(|t| t == CREDENTIAL_TYPE) {
+            return Err(ValidationError::MissingType(CREDENTIAL_TYPE));
+        }
+
+        if self.credential_subject.is_empty() {
+            return Err(ValidationError::MissingSubject);
+        }
+
+        match version {
+            Version::V2 => {
+                if self.issuance_date.is_some() {
+                    return Err(ValidationError::UnexpectedProperty("issuanceDate"));
+                }
+                if self.expiration_date.is_some() {
+                    return Err(ValidationError::UnexpectedProperty("expirationDate"));
+                }
+            }
+            Version::V1_1 => {
+                if self.issuance_date.is_none() {
+                    return Err(ValidationError::MissingIssuanceDate);
+                }
+                if self.valid_from.is_some() {
+                    return Err(ValidationError::UnexpectedProperty("validFrom"));
+                }
+                if self.valid_until.is_some() {
+                    return Err(ValidationError::UnexpectedProperty("validUntil"));
+                }
+            }
+        }
+
+        if let (Some(from), Some(until)) = (self.valid_from(), self.valid_until())
+            && until < from
+        {
+            return Err(ValidationError::InvalidValidityPeriod);
+        }
+
+        Ok(())
+    }
+}
+
+/// The issuer of a credential, either a DID or an object with an `id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issuer {
+    pub id:         Did,
+    /// Additional properties, such as a `name`. If empty, the issuer is
+    /// serialized as a plain DID.
+    pub properties: Map<String, Value>,
+}
+
+impl From<Did> for Issuer {
+    fn from(id: Did) -> Self {
+        Self {
+            id,
+            properties: Map::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum IssuerRepr {
+    Id(Did),
+    Object {
+        id:         Did,
+        #[serde(flatten)]
+        properties: Map<String, Value>,
+    },
+}
+
+impl Serialize for Issuer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.properties.is_empty() {
+            IssuerRepr::Id(self.id.clone()).serialize(serializer)
+        } else {
+            IssuerRepr::Object {
+                id:         self.id.clone(),
+                properties: self.properties.clone(),
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Issuer {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(match IssuerRepr::deserialize(deserializer)? {
+            IssuerRepr::Id(id) => Self::from(id),
+            IssuerRepr::Object { id, properties } => Self { id, properties },
+        })
+    }
+}
+
+/// A [credential subject](https://www.w3.org/TR/vc-data-model-2.0/#credential-subject).
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct CredentialSubject {
+    pub id:     Option<Did>,
+    /// Claims about the subject.
+    #[serde(flatten)]
+    pub claims: Map<String, Value>,
+}
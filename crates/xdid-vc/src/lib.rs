@@ -0,0 +1,87 @@
+//! [W3C Verifiable Credentials](https://www.w3.org/TR/vc-data-model-2.0/) data model types.
+//!
+//! Supports both [VC Data Model 2.0](https://www.w3.org/TR/vc-data-model-2.0/)
+//! and [1.1](https://www.w3.org/TR/vc-data-model-1.1/).
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::{
+    OneOrMany,
+    serde_as,
+    skip_serializing_none,
+};
+use thiserror::Error;
+
+mod credential;
+mod presentation;
+
+pub use credential::*;
+pub use presentation::*;
+
+/// Base context of the VC Data Model 2.0.
+pub const CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+/// Base context of the VC Data Model 1.1.
+pub const CONTEXT_V1: &str = "https://www.w3.org/2018/credentials/v1";
+
+/// Version of the VC data model, determined by the base `@context`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Version {
+    V1_1,
+    V2,
+}
+
+/// An entry in a JSON-LD `@context`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Context {
+    Url(String),
+    Object(Map<String, Value>),
+}
+
+impl Context {
+    fn version(contexts: &[Self]) -> Option<Version> {
+        match contexts.first() {
+            Some(Self::Url(url)) if url == CONTEXT_V2 => Some(Version::V2),
+            Some(Self::Url(url)) if url == CONTEXT_V1 => Some(Version::V1_1),
+            _ => None,
+        }
+    }
+}
+
+/// A typed object, such as a `credentialStatus`, `credentialSchema`, or
+/// `evidence` entry.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TypedObject {
+    pub id:         Option<String>,
+    #[serde(rename = "type")]
+    #[serde_as(as = "OneOrMany<_>")]
+    pub typ:        Vec<String>,
+    #[serde(flatten)]
+    pub properties: Map<String, Value>,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("first @context must be {CONTEXT_V2} or {CONTEXT_V1}")]
+    InvalidContext,
+    #[error("type must include {0}")]
+    MissingType(&'static str),
+    #[error("credentialSubject must not be empty")]
+    MissingSubject,
+    #[error("issuanceDate is required by VC Data Model 1.1")]
+    MissingIssuanceDate,
+    #[error("{0} is not part of this VC data model version")]
+    UnexpectedProperty(&'static str),
+    #[error("validity period ends before it starts")]
+    InvalidValidityPeriod,
+    #[error("invalid credential {index}: {error}")]
+    InvalidCredential { index: usize, error: Box<Self> },
+}
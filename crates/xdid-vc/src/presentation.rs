@@ -0,0 +1,117 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::{
+    OneOrMany,
+    serde_as,
+    skip_serializing_none,
+};
+use xdid_core::did::Did;
+
+use crate::{
+    CONTEXT_V2,
+    Context,
+    TypedObject,
+    ValidationError,
+    VerifiableCredential,
+    Version,
+};
+
+pub const PRESENTATION_TYPE: &str = "VerifiablePresentation";
+
+/// A [verifiable presentation](https://www.w3.org/TR/vc-data-model-2.0/#presentations).
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiablePresentation {
+    #[serde(rename = "@context")]
+    pub context:               Vec<Context>,
+    pub id:                    Option<String>,
+    #[serde(rename = "type")]
+    #[serde_as(as = "OneOrMany<_>")]
+    pub typ:                   Vec<String>,
+    pub holder:                Option<Did>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub verifiable_credential: Option<Vec<PresentedCredential>>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub terms_of_use:          Option<Vec<TypedObject>>,
+    /// Additional properties, such as an embedded `proof`.
+    #[serde(flatten)]
+    pub properties:            Map<String, Value>,
+}
+
+impl VerifiablePresentation {
+    /// Creates a VC Data Model 2.0 presentation.
+    #[must_use]
+    pub fn new(holder: Option<Did>, credentials: Vec<PresentedCredential>) -> Self {
+        Self {
+            context: vec![Context::Url(CONTEXT_V2.into())],
+            id: None,
+            typ: vec![PRESENTATION_TYPE.into()],
+            holder,
+            verifiable_credential: Some(credentials),
+            terms_of_use: None,
+            properties: Map::new(),
+        }
+    }
+
+    /// Data model version, determined by the base `@context`.
+    #[must_use]
+    pub fn version(&self) -> Option<Version> {
+        Context::version(&self.context)
+    }
+
+    /// Checks that the presentation, and every embedded credential, is
+    /// structurally valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first rule the presentation violates.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.version().ok_or(ValidationError::InvalidContext)?;
+
+        if !self.typ.iter().any(|t| t == PRESENTATION_TYPE) {
+            return Err(ValidationError::MissingType(PRESENTATION_TYPE));
+        }
+
+        for (index, credential) in self
+            .verifiable_credential
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            if let PresentedCredential::Embedded(credential) = credential {
+                credential
+                    .validate()
+                    .map_err(|error| ValidationError::InvalidCredential {
+                        index,
+                        error: Box::new(error),
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A credential within a presentation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum PresentedCredential {
+    /// A credential embedded as JSON.
+    Embedded(Box<VerifiableCredential>),
+    /// A credential secured as a compact JWT (VC Data Model 1.1).
+    Jwt(String),
+    /// An enveloped credential, such as an `EnvelopedVerifiableCredential`
+    /// (VC Data Model 2.0).
+    Enveloped(TypedObject),
+}
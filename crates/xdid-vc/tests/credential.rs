@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+use xdid_core::did::Did;
+use xdid_vc::{
+    Context,
+    CredentialSubject,
+    PresentedCredential,
+    ValidationError,
+    VerifiableCredential,
+    VerifiablePresentation,
+    Version,
+};
+
+fn roundtrip<T>(raw: &[u8]) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let value: T = serde_json::from_slice(raw).expect("deserialization should succeed");
+
+    let expected: Value = serde_json::from_slice(raw).expect("deserialization should succeed");
+    let actual = serde_json::to_value(&value).expect("serialization should succeed");
+    assert_eq!(actual, expected);
+
+    value
+}
+
+#[test]
+fn test_credential_v2_serde() {
+    const RAW: &[u8] = include_bytes!("./credential-v2.json");
+
+    let vc: VerifiableCredential = roundtrip(RAW);
+    assert_eq!(vc.version(), Some(Version::V2));
+    assert_eq!(
+        vc.issuer.id.to_string(),
+        "did:example:76e12ec712ebc6f1c221ebfeb1f"
+    );
+    assert_eq!(vc.credential_status.as_ref().map(Vec::len), Some(1));
+    vc.validate().expect("credential should be valid");
+}
+
+#[test]
+fn test_credential_v1_serde() {
+    const RAW: &[u8] = include_bytes!("./credential-v1.json");
+
+    let vc: VerifiableCredential = roundtrip(RAW);
+    assert_eq!(vc.version(), Some(Version::V1_1));
+    assert_eq!(vc.credential_subject.len(), 2);
+    assert!(vc.properties.contains_key("proof"));
+    assert_eq!(vc.valid_until(), vc.expiration_date);
+    vc.validate().expect("credential should be valid");
+}
+
+#[test]
+fn test_presentation_v2_serde() {
+    const RAW: &[u8] = include_bytes!("./presentation-v2.json");
+
+    let vp: VerifiablePresentation = roundtrip(RAW);
+    assert_eq!(vp.version(), Some(Version::V2));
+
+    let credentials = vp.verifiable_credential.as_deref().unwrap_or_default();
+    assert!(matches!(credentials[0], PresentedCredential::Embedded(_)));
+    assert!(matches!(credentials[1], PresentedCredential::Enveloped(_)));
+    assert!(matches!(credentials[2], PresentedCredential::Jwt(_)));
+    vp.validate().expect("presentation should be valid");
+}
+
+fn credential() -> VerifiableCredential {
+    let issuer = Did::from_str("did:example:issuer").expect("valid DID");
+    let subject = CredentialSubject {
+        id:     Some(Did::from_str("did:example:subject").expect("valid DID")),
+        claims: serde_json::Map::new(),
+    };
+    VerifiableCredential::new(issuer, subject)
+}
+
+#[test]
+fn test_credential_validation() {
+    credential().validate().expect("credential should be valid");
+
+    let mut vc = credential();
+    vc.context = vec![Context::Url("https://example.com".to_string())];
+    assert_eq!(vc.validate(), Err(ValidationError::InvalidContext));
+
+    let mut vc = credential();
+    vc.typ = vec!["ExampleCredential".to_string()];
+    assert_eq!(
+        vc.validate(),
+        Err(ValidationError::MissingType("VerifiableCredential"))
+    );
+
+    let mut vc = credential();
+    vc.credential_subject.clear();
+    assert_eq!(vc.validate(), Err(ValidationError::MissingSubject));
+
+    let mut vc = credential();
+    vc.issuance_date = Some(time::OffsetDateTime::UNIX_EPOCH);
+    assert_eq!(
+        vc.validate(),
+        Err(ValidationError::UnexpectedProperty("issuanceDate"))
+    );
+
+    let mut vc = credential();
+    vc.valid_from = Some(time::OffsetDateTime::UNIX_EPOCH + time::Duration::DAY);
+    vc.valid_until = Some(time::OffsetDateTime::UNIX_EPOCH);
+    assert_eq!(vc.validate(), Err(ValidationError::InvalidValidityPeriod));
+}
+
+#[test]
+fn test_presentation_validation() {
+    let mut invalid = credential();
+    invalid.credential_subject.clear();
+
+    let vp = VerifiablePresentation::new(
+        None,
+        vec![
+            PresentedCredential::Embedded(Box::new(credential())),
+            PresentedCredential::Embedded(Box::new(invalid)),
+        ],
+    );
+
+    assert_eq!(
+        vp.validate(),
+        Err(ValidationError::InvalidCredential {
+            index: 1,
+            error: Box::new(ValidationError::MissingSubject),
+        })
+    );
+}
@@ -0,0 +1,676 @@
+//! File-backed caching of DID resolution, persisting results across
+//! restarts.
+//!
+//! Each result is stored as a JSON file in the cache directory, along with
+//! its resolution and document metadata. The cache is best-effort: I/O
+//! errors are treated as cache misses. [`FileCachedMethod`] runs file I/O on
+//! a thread pool, so it does not block the executor.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+        atomic::{
+            AtomicU64,
+            Ordering,
+        },
+    },
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use xdid_core::{
+    Method,
+    MethodFuture,
+    ResolutionError,
+    did::Did,
+    document::Document,
+    resolution::{
+        ResolutionOptions,
+        ResolutionResult,
+    },
+};
+
+use crate::resolver::DidResolver;
+
+/// Configuration for a [`FileCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileCacheConfig {
+    /// How long results are cached for, if the method does not provide an
+    /// HTTP `Cache-Control` header.
+    pub default_ttl: Duration,
+    /// Maximum number of stored results.
+    pub max_entries: usize,
+    /// Maximum total size of stored results, in bytes.
+    pub max_bytes:   u64,
+    /// Return expired results if resolution fails with a transient error,
+    /// [`ResolutionError::InternalError`] or [`ResolutionError::Timeout`].
+    pub serve_stale: bool,
+}
+
+impl Default for FileCacheConfig {
+    fn default() -> Self {
+        Self {
+            default_ttl: Duration::from_hours(1),
+            max_entries: 4096,
+            max_bytes:   64 * 1024 * 1024,
+            serve_stale: false,
+        }
+    }
+}
+
+/// Directory of cached [`ResolutionResult`]s, keyed by [`Did`] and
+/// optionally `versionId`.
+///
+/// When a limit in [`FileCacheConfig`] is exceeded, the least recently
+/// written results are removed, leaving some headroom so the directory is not
+/// scanned on every write. Temporary files left behind by interrupted writes
+/// are removed at the same time.
+///
+/// The methods of this type block on file I/O.
+#[derive(Clone)]
+pub struct FileCache {
+    dir:    Arc<PathBuf>,
+    config: FileCacheConfig,
+    usage:  Arc<Mutex<Usage>>,
+}
+
+/// Approximate number and total size of stored results, updated on each
+/// write and recounted when pruning.
+#[derive(Default)]
+struct Usage {
+    entries: usize,
+    bytes:   u64,
+}
+
+/// Distinguishes temporary files of concurrent writes within a process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Age after which a temporary file is assumed to be from an interrupted
+/// write, rather than one in progress.
+const TMP_MAX_AGE: Duration = Duration::from_mins(1);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileEntry {
+    did:        Did,
+    version_id: Option<String>,
+    /// Unix timestamp in milliseconds.
+    expires:    u64,
+    result:     ResolutionResult,
+}
+
+impl FileCache {
+    /// Opens a cache in the given directory, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(dir: impl Into<PathBuf>, config: FileCacheConfig) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let cache = Self {
+            dir: Arc::new(dir),
+            config,
+            usage: Arc::default(),
+        };
+        cache.recount()?;
+
+        Ok(cache)
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the cached result for a DID, if it has not expired.
+    #[must_use]
+    pub fn get(&self, did: &Did, version_id: Option<&str>) -> Option<ResolutionResult> {
+        self.read(did, version_id)
+            .filter(|entry| entry.expires > unix_now())
+            .map(|entry| entry.result)
+    }
+
+    /// Removes a DID from the cache.
+    pub fn invalidate(&self, did: &Did, version_id: Option<&str>) {
+        let path = self.path(did, version_id);
+        let Ok(metadata) = fs::metadata(&path) else {
+            return;
+        };
+
+        if fs::remove_file(path).is_ok() {
+            let mut usage = self.usage();
+            usage.entries = usage.entries.saturating_sub(1);
+            usage.bytes = usage.bytes.saturating_sub(metadata.len());
+        }
+    }
+
+    /// Removes all DIDs from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, or a file cannot be
+    /// removed.
+    pub fn clear(&self) -> io::Result<()> {
+        for (path, ..) in self.files()? {
+            fs::remove_file(path)?;
+        }
+        *self.usage() = Usage::default();
+        Ok(())
+    }
+
+    fn path(&self, did: &Did, version_id: Option<&str>) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(did.to_string());
+        if let Some(version_id) = version_id {
+            hasher.update([0]);
+            hasher.update(version_id);
+        }
+
+        let name = hasher
+            .finalize()
+            .iter()
+            .fold(String::new(), |mut name, byte| {
+                let _ = write!(name, "{byte:02x}");
+                name
+            });
+
+        self.dir.join(name).with_extension("json")
+    }
+
+    fn read(&self, did: &Did, version_id: Option<&str>) -> Option<FileEntry> {
+        let data = fs::read(self.path(did, version_id)).ok()?;
+        let entry = serde_json::from_slice::<FileEntry>(&data).ok()?;
+
+        // Guard against hash collisions.
+        (entry.did == *did && entry.version_id.as_deref() == version_id).then_some(entry)
+    }
+
+    fn write(&self, did: &Did, version_id: Option<&str>, result: &ResolutionResult) {
+        let ttl = super::ttl(result).unwrap_or(self.config.default_ttl);
+        if ttl.is_zero() {
+            return;
+        }
+
+        let entry = FileEntry {
+            did:        did.clone(),
+            version_id: version_id.map(ToString::to_string),
+            expires:    unix_now().saturating_add(millis(ttl)),
+            result:     result.clone(),
+        };
+        let Ok(data) = serde_json::to_vec(&entry) else {
+            return;
+        };
+
+        // Write to a unique file then rename, so readers never see a partial
+        // file, and concurrent writers do not share a temporary file.
+        let path = self.path(did, version_id);
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let previous = fs::metadata(&path).ok().map(|metadata| metadata.len());
+
+        if fs::write(&tmp, &data).is_err() || fs::rename(&tmp, &path).is_err() {
+            let _ = fs::remove_file(tmp);
+            return;
+        }
+
+        let exceeded = {
+            let mut usage = self.usage();
+            match previous {
+                Some(len) => usage.bytes = usage.bytes.saturating_sub(len),
+                None => usage.entries += 1,
+            }
+            usage.bytes += data.len() as u64;
+
+            usage.entries > self.config.max_entries || usage.bytes > self.config.max_bytes
+        };
+
+        if exceeded {
+            let _ = self.prune();
+        }
+    }
+
+    /// Removes the least recently written files until the cache is 10% below
+    /// its limits, along with orphaned temporary files.
+    fn prune(&self) -> io::Result<()> {
+        self.remove_orphans()?;
+
+        let max_entries = self.config.max_entries - self.config.max_entries / 10;
+        let max_bytes = self.config.max_bytes - self.config.max_bytes / 10;
+
+        let mut files = self.files()?;
+        files.sort_by_key(|(_, _, modified)| std::cmp::Reverse(*modified));
+
+        let mut kept = Usage::default();
+        for (path, len, _) in files {
+            if kept.entries < max_entries && kept.bytes + len <= max_bytes {
+                kept.entries += 1;
+                kept.bytes += len;
+            } else {
+                let _ = fs::remove_file(path);
+            }
+        }
+
+        *self.usage() = kept;
+        Ok(())
+    }
+
+    /// Removes temporary files older than [`TMP_MAX_AGE`].
+    fn remove_orphans(&self) -> io::Result<()> {
+        let now = SystemTime::now();
+
+        for entry in fs::read_dir(self.dir.as_ref())? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "tmp") {
+                continue;
+            }
+
+            let Ok(modified) = fs::metadata(&path).and_then(|metadata| metadata.modified()) else {
+                continue;
+            };
+            if now.duration_since(modified).unwrap_or_default() > TMP_MAX_AGE {
+                let _ = fs::remove_file(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Counts the stored results.
+    fn recount(&self) -> io::Result<()> {
+        let files = self.files()?;
+        *self.usage() = Usage {
+            entries: files.len(),
+            bytes:   files.iter().map(|(_, len, _)| len).sum(),
+        };
+        Ok(())
+    }
+
+    fn usage(&self) -> MutexGuard<'_, Usage> {
+        self.usage.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lists cached files, with their size and modification time.
+    fn files(&self) -> io::Result<Vec<(PathBuf, u64, SystemTime)>> {
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(self.dir.as_ref())? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let metadata = entry.metadata()?;
+            files.push((path, metadata.len(), metadata.modified()?));
+        }
+
+        Ok(files)
+    }
+}
+
+/// Current Unix timestamp in milliseconds.
+fn unix_now() -> u64 {
+    millis(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default(),
+    )
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// A [`Method`] that caches its resolutions in a [`FileCache`].
+///
+/// Resolutions are cached if their [`ResolutionOptions`] are default, apart
+/// from `versionId`. Others are passed through to the method.
+pub struct FileCachedMethod {
+    method: Box<dyn Method>,
+    cache:  FileCache,
+}
+
+impl FileCachedMethod {
+    #[must_use]
+    pub fn new(method: impl Method + 'static, cache: FileCache) -> Self {
+        Self::boxed(Box::new(method), cache)
+    }
+
+    #[must_use]
+    pub fn boxed(method: Box<dyn Method>, cache: FileCache) -> Self {
+        Self { method, cache }
+    }
+
+    #[must_use]
+    pub const fn cache(&self) -> &FileCache {
+        &self.cache
+    }
+}
+
+impl Method for FileCachedMethod {
+    fn method_name(&self) -> &'static str {
+        self.method.method_name()
+    }
+
+    fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
+        let fut = self.resolve_with_metadata(did, ResolutionOptions::default());
+        Box::pin(async move { fut.await.map(|result| result.document) })
+    }
+
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        let version_id = options.version_id.clone();
+        let is_cacheable = ResolutionOptions {
+            version_id: None,
+            ..options.clone()
+        } == ResolutionOptions::default();
+
+        if !is_cacheable {
+            return self.method.resolve_with_metadata(did, options);
+        }
+
+        let cache = self.cache.clone();
+        let fut = self.method.resolve_with_metadata(did.clone(), options);
+
+        Box::pin(async move {
+            let (did, version_id, entry) = {
+                let cache = cache.clone();
+                blocking::unblock(move || {
+                    let entry = cache.read(&did, version_id.as_deref());
+                    (did, version_id, entry)
+                })
+                .await
+            };
+
+            if let Some(entry) = &entry
+                && entry.expires > unix_now()
+            {
+                return Ok(entry.result.clone());
+            }
+
+            match fut.await {
+                Ok(result) => {
+                    let cache = cache.clone();
+                    let written = result.clone();
+                    blocking::unblock(move || cache.write(&did, version_id.as_deref(), &written))
+                        .await;
+                    Ok(result)
+                }
+                Err(e) => match entry {
+                    Some(entry)
+                        if cache.config.serve_stale
+                            && matches!(
+                                e,
+                                ResolutionError::InternalError(_) | ResolutionError::Timeout
+                            ) =>
+                    {
+                        Ok(entry.result)
+                    }
+                    _ => Err(e),
+                },
+            }
+        })
+    }
+}
+
+impl DidResolver {
+    /// Wraps every method of the resolver in a [`FileCachedMethod`] using
+    /// the provided cache.
+    ///
+    /// To also cache in memory, call [`DidResolver::with_cache`] after this.
+    #[must_use]
+    pub fn with_file_cache(mut self, cache: &FileCache) -> Self {
+        self.methods = self
+            .methods
+            .into_iter()
+            .map(|method| {
+                Box::new(FileCachedMethod::boxed(method, cache.clone())) as Box<dyn Method>
+            })
+            .collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::test_utils::MethodMock;
+
+    fn did() -> Did {
+        Did::from_str("did:counter:a").expect("valid DID")
+    }
+
+    fn cache(dir: &TempDir, config: FileCacheConfig) -> FileCache {
+        FileCache::new(dir.path(), config).expect("cache should open")
+    }
+
+    #[tokio::test]
+    async fn test_persists_across_instances() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let counter = MethodMock::new("counter");
+
+        let method =
+            FileCachedMethod::new(counter.clone(), cache(&dir, FileCacheConfig::default()));
+        let first = method
+            .resolve_with_metadata(did(), ResolutionOptions::default())
+            .await
+            .expect("resolution should succeed");
+
+        let method =
+            FileCachedMethod::new(counter.clone(), cache(&dir, FileCacheConfig::default()));
+        let second = method
+            .resolve_with_metadata(did(), ResolutionOptions::default())
+            .await
+            .expect("resolution should succeed");
+
+        assert_eq!(first, second);
+        assert_eq!(counter.resolutions(), 1);
+
+        method.cache().invalidate(&did(), None);
+        assert!(method.cache().get(&did(), None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_version_key() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let counter = MethodMock::new("counter");
+        let method =
+            FileCachedMethod::new(counter.clone(), cache(&dir, FileCacheConfig::default()));
+
+        for version_id in [None, Some("1"), Some("1"), Some("2"), None] {
+            let options = ResolutionOptions {
+                version_id: version_id.map(ToString::to_string),
+                ..Default::default()
+            };
+            let result = method
+                .resolve_with_metadata(did(), options)
+                .await
+                .expect("resolution should succeed");
+            assert_eq!(result.document_metadata.version_id.as_deref(), version_id);
+        }
+
+        assert_eq!(counter.resolutions(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_expiry_and_serve_stale() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let counter = MethodMock::new("counter");
+
+        let config = FileCacheConfig {
+            default_ttl: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let method = FileCachedMethod::new(counter.clone(), cache(&dir, config));
+        method
+            .resolve(did())
+            .await
+            .expect("resolution should succeed");
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(method.cache().get(&did(), None).is_none());
+
+        counter.fail_with(Some(|| ResolutionError::internal("offline")));
+        let res = method.resolve(did()).await;
+        assert!(matches!(res, Err(ResolutionError::InternalError(_))));
+
+        let method = FileCachedMethod::new(
+            counter.clone(),
+            cache(
+                &dir,
+                FileCacheConfig {
+                    serve_stale: true,
+                    ..config
+                },
+            ),
+        );
+        let doc = method
+            .resolve(did())
+            .await
+            .expect("stale result should be served");
+        assert_eq!(doc.id, did());
+        assert_eq!(counter.resolutions(), 3);
+
+        // Only transient errors fall back to stale results.
+        counter.fail_with(Some(|| ResolutionError::NotFound(None)));
+        let res = method.resolve(did()).await;
+        assert!(matches!(res, Err(ResolutionError::NotFound(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_writes() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let method = FileCachedMethod::new(
+            MethodMock::new("counter"),
+            cache(&dir, FileCacheConfig::default()),
+        );
+
+        let results = futures_util::future::join_all((0..16).map(|_| method.resolve(did()))).await;
+        assert!(results.iter().all(Result::is_ok));
+
+        assert_eq!(method.cache().files().expect("files should list").len(), 1);
+        assert!(method.cache().get(&did(), None).is_some());
+
+        let leftover = fs::read_dir(dir.path())
+            .expect("dir should list")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "tmp"))
+            .count();
+        assert_eq!(leftover, 0);
+    }
+
+    #[tokio::test]
+    async fn test_size_limits() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let method = FileCachedMethod::new(
+            MethodMock::new("counter"),
+            cache(
+                &dir,
+                FileCacheConfig {
+                    max_entries: 2,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        for id in ["a", "b", "c"] {
+            let did = Did::from_str(&format!("did:counter:{id}")).expect("valid DID");
+            method
+                .resolve(did)
+                .await
+                .expect("resolution should succeed");
+        }
+        assert_eq!(method.cache().files().expect("files should list").len(), 2);
+
+        let method = FileCachedMethod::new(
+            MethodMock::new("counter"),
+            cache(
+                &dir,
+                FileCacheConfig {
+                    max_bytes: 0,
+                    ..Default::default()
+                },
+            ),
+        );
+        method
+            .resolve(Did::from_str("did:counter:d").expect("valid DID"))
+            .await
+            .expect("resolution should succeed");
+        assert!(
+            method
+                .cache()
+                .files()
+                .expect("files should list")
+                .is_empty()
+        );
+
+        method.cache().clear().expect("cache should clear");
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_tmp() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        let method = FileCachedMethod::new(
+            MethodMock::new("counter"),
+            cache(
+                &dir,
+                FileCacheConfig {
+                    max_entries: 1,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        let orphan = dir.path().join("orphan.tmp");
+        let pending = dir.path().join("pending.tmp");
+        fs::write(&pending, b"").expect("file should be written");
+        fs::File::create(&orphan)
+            .and_then(|file| file.set_modified(SystemTime::now() - TMP_MAX_AGE * 2))
+            .expect("file should be written");
+
+        for id in ["a", "b"] {
+            let did = Did::from_str(&format!("did:counter:{id}")).expect("valid DID");
+            method
+                .resolve(did)
+                .await
+                .expect("resolution should succeed");
+        }
+
+        assert!(!orphan.exists());
+        assert!(pending.exists());
+    }
+}
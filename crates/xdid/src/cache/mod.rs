@@ -0,0 +1,500 @@
+//! In-memory caching of DID resolution.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), xdid::resolver::MethodError> {
+//! use xdid::{
+//!     cache::ResolutionCache,
+//!     resolver::DidResolver,
+//! };
+//!
+//! let cache = ResolutionCache::default();
+//! let resolver = DidResolver::new()?.with_cache(&cache);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    time::Duration,
+};
+
+use futures_util::{
+    FutureExt,
+    future::Shared,
+};
+use lru::LruCache;
+use web_time::Instant;
+use xdid_core::{
+    Method,
+    MethodFuture,
+    ResolutionError,
+    did::Did,
+    document::Document,
+    resolution::{
+        ResolutionOptions,
+        ResolutionResult,
+    },
+};
+
+use crate::resolver::DidResolver;
+
+#[cfg(all(feature = "file-cache", not(target_family = "wasm")))]
+pub mod file;
+
+type SharedResolution = Shared<MethodFuture<Result<ResolutionResult, ResolutionError>>>;
+
+/// Configuration for a [`ResolutionCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Maximum number of cached results. The least recently used result is
+    /// evicted when full.
+    pub capacity:    NonZeroUsize,
+    /// How long results are cached for, if the method does not provide an
+    /// HTTP `Cache-Control` header.
+    pub default_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity:    NonZeroUsize::new(1024).expect("capacity is non-zero"),
+            default_ttl: Duration::from_mins(5),
+        }
+    }
+}
+
+/// LRU cache of [`ResolutionResult`]s with expiry, shared between
+/// [`CachedMethod`]s.
+///
+/// Cloning the cache returns a handle to the same entries, which can be
+/// used to invalidate them.
+#[derive(Clone)]
+pub struct ResolutionCache {
+    config: CacheConfig,
+    state:  Arc<Mutex<CacheState>>,
+}
+
+struct CacheState {
+    entries: LruCache<Did, CacheEntry>,
+    /// In-flight resolutions, tagged with an id so results from invalidated
+    /// resolutions are not stored.
+    pending: HashMap<Did, (u64, SharedResolution)>,
+    next_id: u64,
+}
+
+struct CacheEntry {
+    result:  ResolutionResult,
+    expires: Instant,
+}
+
+impl Default for ResolutionCache {
+    fn default() -> Self {
+        Self::new(CacheConfig::default())
+    }
+}
+
+impl ResolutionCache {
+    #[must_use]
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(CacheState {
+                entries: LruCache::new(config.capacity),
+                pending: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Removes a DID from the cache. Resolutions of it that are in flight
+    /// will not be cached.
+    pub fn invalidate(&self, did: &Did) {
+        let mut state = self.lock();
+        state.entries.pop(did);
+        state.pending.remove(did);
+    }
+
+    /// Removes all DIDs from the cache.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.pending.clear();
+    }
+
+    /// Number of cached results, including expired ones that have not been
+    /// evicted yet.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached result for a DID, or a future resolving it.
+    /// Concurrent callers for the same DID share one resolution.
+    fn get_or_resolve(&self, method: &dyn Method, did: Did) -> SharedResolution {
+        let mut state = self.lock();
+
+        if let Some(entry) = state.entries.get(&did) {
+            if entry.expires > Instant::now() {
+                let result = entry.result.clone();
+                let fut: MethodFuture<_> = Box::pin(async move { Ok(result) });
+                return fut.shared();
+            }
+            state.entries.pop(&did);
+        }
+
+        if let Some((_, fut)) = state.pending.get(&did) {
+            return fut.clone();
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+
+        let inner = method.resolve_with_metadata(did.clone(), ResolutionOptions::default());
+        let state_ref = Arc::downgrade(&self.state);
+        let default_ttl = self.config.default_ttl;
+        let key = did.clone();
+
+        let fut: MethodFuture<_> = Box::pin(async move {
+            let res = inner.await;
+            if let Some(state) = state_ref.upgrade() {
+                state
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .complete(&key, id, &res, default_ttl);
+            }
+            res
+        });
+        let fut = fut.shared();
+
+        state.pending.insert(did, (id, fut.clone()));
+        fut
+    }
+}
+
+impl CacheState {
+    fn complete(
+        &mut self,
+        did: &Did,
+        id: u64,
+        res: &Result<ResolutionResult, ResolutionError>,
+        default_ttl: Duration,
+    ) {
+        if self
+            .pending
+            .get(did)
+            .is_none_or(|(pending, _)| *pending != id)
+        {
+            return;
+        }
+        self.pending.remove(did);
+
+        let Ok(result) = res else {
+            return;
+        };
+
+        let ttl = ttl(result).unwrap_or(default_ttl);
+        if ttl.is_zero() {
+            return;
+        }
+
+        self.entries.put(
+            did.clone(),
+            CacheEntry {
+                result:  result.clone(),
+                expires: Instant::now() + ttl,
+            },
+        );
+    }
+}
+
+/// Reads the TTL of a result from its HTTP `Cache-Control` header.
+fn ttl(result: &ResolutionResult) -> Option<Duration> {
+    let cache_control = result.resolution_metadata.cache_control()?;
+
+    let mut max_age = None;
+    for directive in cache_control.split(',') {
+        let directive = directive.trim().to_ascii_lowercase();
+        match directive.split_once('=') {
+            Some(("max-age", value)) => {
+                max_age = value
+                    .trim_matches('"')
+                    .parse()
+                    .ok()
+                    .map(Duration::from_secs);
+            }
+            None if directive == "no-store" || directive == "no-cache" => {
+                return Some(Duration::ZERO);
+            }
+            _ => {}
+        }
+    }
+
+    max_age
+}
+
+/// A [`Method`] that caches its resolutions in a [`ResolutionCache`].
+///
+/// Only resolutions with default [`ResolutionOptions`] are cached. Others,
+/// such as those selecting a version, are passed through to the method.
+pub struct CachedMethod {
+    method: Box<dyn Method>,
+    cache:  ResolutionCache,
+}
+
+impl CachedMethod {
+    #[must_use]
+    pub fn new(method: impl Method + 'static, cache: ResolutionCache) -> Self {
+        Self::boxed(Box::new(method), cache)
+    }
+
+    #[must_use]
+    pub fn boxed(method: Box<dyn Method>, cache: ResolutionCache) -> Self {
+        Self { method, cache }
+    }
+
+    #[must_use]
+    pub const fn cache(&self) -> &ResolutionCache {
+        &self.cache
+    }
+}
+
+impl Method for CachedMethod {
+    fn method_name(&self) -> &'static str {
+        self.method.method_name()
+    }
+
+    fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
+        let fut = self.cache.get_or_resolve(self.method.as_ref(), did);
+        Box::pin(async move { fut.await.map(|result| result.document) })
+    }
+
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        if options != ResolutionOptions::default() {
+            return self.method.resolve_with_metadata(did, options);
+        }
+
+        Box::pin(self.cache.get_or_resolve(self.method.as_ref(), did))
+    }
+}
+
+impl DidResolver {
+    /// Wraps every method of the resolver in a [`CachedMethod`] using the
+    /// provided cache.
+    #[must_use]
+    pub fn with_cache(mut self, cache: &ResolutionCache) -> Self {
+        self.methods = self
+            .methods
+            .into_iter()
+            .map(|method| Box::new(CachedMethod::boxed(method, cache.clone())) as Box<dyn Method>)
+            .collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::test_utils::MethodMock;
+
+    fn cached_method(
+        cache_control: Option<&'static str>,
+        config: CacheConfig,
+    ) -> (CachedMethod, MethodMock) {
+        let mut mock = MethodMock::new("counter");
+        if let Some(cache_control) = cache_control {
+            mock = mock.with_cache_control(cache_control);
+        }
+        let method = CachedMethod::new(mock.clone(), ResolutionCache::new(config));
+        (method, mock)
+    }
+
+    fn did(id: &str) -> Did {
+        Did::from_str(&format!("did:counter:{id}")).expect("valid DID")
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit() {
+        let (method, mock) = cached_method(None, CacheConfig::default());
+
+        for _ in 0..3 {
+            let doc = method
+                .resolve(did("a"))
+                .await
+                .expect("resolution should succeed");
+            assert_eq!(doc.id, did("a"));
+        }
+        assert_eq!(mock.resolutions(), 1);
+
+        method
+            .resolve(did("b"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expiry() {
+        let (method, mock) = cached_method(
+            None,
+            CacheConfig {
+                default_ttl: Duration::from_millis(20),
+                ..Default::default()
+            },
+        );
+
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+
+        assert_eq!(mock.resolutions(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_control() {
+        let (method, mock) = cached_method(Some("no-store"), CacheConfig::default());
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 2);
+
+        let (method, mock) = cached_method(Some("public, max-age=0"), CacheConfig::default());
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 2);
+
+        let (method, mock) = cached_method(
+            Some("public, max-age=3600"),
+            CacheConfig {
+                default_ttl: Duration::ZERO,
+                ..Default::default()
+            },
+        );
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction() {
+        let (method, mock) = cached_method(
+            None,
+            CacheConfig {
+                capacity: NonZeroUsize::new(2).expect("capacity is non-zero"),
+                ..Default::default()
+            },
+        );
+
+        for id in ["a", "b", "a", "c", "a", "b"] {
+            method
+                .resolve(did(id))
+                .await
+                .expect("resolution should succeed");
+        }
+
+        // "b" is evicted by "c", as "a" was used more recently.
+        assert_eq!(mock.resolutions(), 4);
+        assert_eq!(method.cache().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_coalescing() {
+        let (method, mock) = cached_method(None, CacheConfig::default());
+
+        let results =
+            futures_util::future::join_all((0..8).map(|_| method.resolve(did("a")))).await;
+        for res in results {
+            res.expect("resolution should succeed");
+        }
+
+        assert_eq!(mock.resolutions(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate() {
+        let (method, mock) = cached_method(None, CacheConfig::default());
+
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        method.cache().invalidate(&did("a"));
+        assert!(method.cache().is_empty());
+
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 2);
+
+        method.cache().clear();
+        method
+            .resolve(did("a"))
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(mock.resolutions(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_options_bypass_cache() {
+        let (method, mock) = cached_method(None, CacheConfig::default());
+
+        let options = ResolutionOptions {
+            accept: Some("application/did+json".to_string()),
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            method
+                .resolve_with_metadata(did("a"), options.clone())
+                .await
+                .expect("resolution should succeed");
+        }
+
+        assert_eq!(mock.resolutions(), 2);
+        assert!(method.cache().is_empty());
+    }
+}
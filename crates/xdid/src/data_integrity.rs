@@ -0,0 +1,653 @@
+//! [Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proofs with DID
+//! keys.
+//!
+//! Supports the JCS-based cryptosuites:
+//!
+//! - [`eddsa-jcs-2022`](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022) for
+//!   Ed25519 keys.
+//! - [`ecdsa-jcs-2019`](https://www.w3.org/TR/vc-di-ecdsa/#ecdsa-jcs-2019) for
+//!   P-256 and P-384 keys.
+//!
+//! RDF-based cryptosuites, such as `ecdsa-rdfc-2019`, are not supported.
+
+use jose_jwk::jose_jwa::Signing;
+use multibase::Base;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::skip_serializing_none;
+use sha2::{
+    Digest,
+    Sha256,
+    Sha384,
+};
+use thiserror::Error;
+use time::OffsetDateTime;
+use xdid_core::{
+    did::Did,
+    did_url::DidUrl,
+    document::VerificationRole,
+    jcs::{
+        self,
+        JcsError,
+    },
+};
+use xdid_method_key::{
+    DidKeyPair,
+    PublicKey,
+};
+
+use crate::{
+    jws::signature_len,
+    resolver::DidResolver,
+    verify::VerifyError,
+};
+
+pub const PROOF_TYPE: &str = "DataIntegrityProof";
+pub const EDDSA_JCS_2022: &str = "eddsa-jcs-2022";
+pub const ECDSA_JCS_2019: &str = "ecdsa-jcs-2019";
+
+/// A Data Integrity [proof](https://www.w3.org/TR/vc-data-integrity/#proofs).
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Proof {
+    #[serde(rename = "@context")]
+    pub context:             Option<Value>,
+    pub id:                  Option<String>,
+    #[serde(rename = "type")]
+    pub typ:                 String,
+    pub cryptosuite:         String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub created:             Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires:             Option<OffsetDateTime>,
+    pub verification_method: DidUrl,
+    pub proof_purpose:       ProofPurpose,
+    pub challenge:           Option<String>,
+    pub domain:              Option<String>,
+    pub nonce:               Option<String>,
+    /// Multibase encoded signature.
+    pub proof_value:         Option<String>,
+    #[serde(flatten)]
+    pub properties:          Map<String, Value>,
+}
+
+/// The reason a proof was created, which determines the
+/// [`VerificationRole`] its key must be listed under.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofPurpose {
+    AssertionMethod,
+    Authentication,
+    CapabilityDelegation,
+    CapabilityInvocation,
+    KeyAgreement,
+}
+
+impl From<ProofPurpose> for VerificationRole {
+    fn from(value: ProofPurpose) -> Self {
+        match value {
+            ProofPurpose::AssertionMethod => Self::Assertion,
+            ProofPurpose::Authentication => Self::Authentication,
+            ProofPurpose::CapabilityDelegation => Self::CapabilityDelegation,
+            ProofPurpose::CapabilityInvocation => Self::CapabilityInvocation,
+            ProofPurpose::KeyAgreement => Self::KeyAgreement,
+        }
+    }
+}
+
+/// Options for [`create_proof`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofOptions {
+    pub purpose:             ProofPurpose,
+    /// Verification method of the signing key. Defaults to the key pair's
+    /// did:key verification method.
+    pub verification_method: Option<DidUrl>,
+    pub created:             Option<OffsetDateTime>,
+    pub expires:             Option<OffsetDateTime>,
+    pub challenge:           Option<String>,
+    pub domain:              Option<String>,
+}
+
+impl Default for ProofOptions {
+    fn default() -> Self {
+        Self {
+            purpose:             ProofPurpose::AssertionMethod,
+            verification_method: None,
+            created:             None,
+            expires:             None,
+            challenge:           None,
+            domain:              None,
+        }
+    }
+}
+
+/// Create a proof over a JSON document. Any existing `proof` on the document
+/// is not covered by the new proof.
+///
+/// # Errors
+///
+/// Returns an error if the document is not a JSON object, the key has no
+/// supported cryptosuite, or signing fails.
+pub fn create_proof(
+    pair: &impl DidKeyPair,
+    document: &impl Serialize,
+    options: &ProofOptions,
+) -> Result<Proof, DataIntegrityError> {
+    let mut document = to_object(document)?;
+    document.remove("proof");
+
+    let public = pair.public();
+    let alg = public.jws_alg().ok_or(DataIntegrityError::UnsupportedKey)?;
+    let cryptosuite = cryptosuite(alg).ok_or(DataIntegrityError::UnsupportedKey)?;
+
+    let verification_method = options.verification_method.clone().unwrap_or_else(|| {
+        let did = public.to_did();
+        DidUrl {
+            fragment: Some(did.method_id.0.clone().into()),
+            did,
+            path_abempty: None,
+            query: None,
+        }
+    });
+
+    let mut proof = Proof {
+        context: document.get("@context").cloned(),
+        id: None,
+        typ: PROOF_TYPE.into(),
+        cryptosuite: cryptosuite.into(),
+        created: options.created,
+        expires: options.expires,
+        verification_method,
+        proof_purpose: options.purpose,
+        challenge: options.challenge.clone(),
+        domain: options.domain.clone(),
+        nonce: None,
+        proof_value: None,
+        properties: Map::new(),
+    };
+
+    let config = to_object(&proof)?;
+    let hash_data = hash_data(alg, &config, &document)?;
+
+    let signature = pair
+        .sign_fixed(&hash_data)
+        .map_err(DataIntegrityError::Signing)?;
+    if signature_len(alg) != Some(signature.len()) {
+        return Err(DataIntegrityError::UnsupportedKey);
+    }
+
+    proof.proof_value = Some(multibase::encode(Base::Base58Btc, signature));
+
+    Ok(proof)
+}
+
+/// Create a proof over a JSON document, and return the secured document. If
+/// the document already has a proof, the new proof is added to the set.
+///
+/// # Errors
+///
+/// Returns an error if the proof cannot be created.
+pub fn add_proof(
+    pair: &impl DidKeyPair,
+    document: &impl Serialize,
+    options: &ProofOptions,
+) -> Result<Value, DataIntegrityError> {
+    let mut document = to_object(document)?;
+    let proof = serde_json::to_value(create_proof(pair, &document, options)?)?;
+
+    let proof = match document.remove("proof") {
+        Some(Value::Array(mut proofs)) => {
+            proofs.push(proof);
+            Value::Array(proofs)
+        }
+        Some(existing) => Value::Array(vec![existing, proof]),
+        None => proof,
+    };
+    document.insert("proof".into(), proof);
+
+    Ok(Value::Object(document))
+}
+
+impl DidResolver {
+    /// Verify every proof on a secured JSON document. Each proof must have
+    /// the given purpose, and its `verificationMethod` must be listed under
+    /// the matching [`VerificationRole`].
+    ///
+    /// Proofs are not bound to any particular DID, so the caller must check
+    /// that each `verificationMethod` belongs to the expected controller, such
+    /// as a credential's issuer, or use [`Self::verify_proof_for`].
+    ///
+    /// Returns the verified proofs, so the caller can check properties such
+    /// as `challenge` or `domain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document has no proofs, or any proof fails to
+    /// verify or has expired.
+    pub async fn verify_proof(
+        &self,
+        document: &(impl Serialize + Sync),
+        purpose: ProofPurpose,
+    ) -> Result<Vec<Proof>, DataIntegrityError> {
+        self.verify_proof_at(document, purpose, OffsetDateTime::now_utc())
+            .await
+    }
+
+    /// Verify every proof on a secured JSON document, like
+    /// [`Self::verify_proof`], checking expiry against `now` instead of the
+    /// current time.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::verify_proof`].
+    pub async fn verify_proof_at(
+        &self,
+        document: &(impl Serialize + Sync),
+        purpose: ProofPurpose,
+        now: OffsetDateTime,
+    ) -> Result<Vec<Proof>, DataIntegrityError> {
+        self.verify_proof_inner(document, None, purpose, now).await
+    }
+
+    /// Verify every proof on a secured JSON document on behalf of `did`. Each
+    /// `verificationMethod` must be listed under the matching
+    /// [`VerificationRole`] of `did`'s document, and may reference a key
+    /// delegated from another DID document.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::verify_proof`]. Also returns an error if a verification
+    /// method is not authorized by `did`.
+    pub async fn verify_proof_for(
+        &self,
+        document: &(impl Serialize + Sync),
+        did: &Did,
+        purpose: ProofPurpose,
+    ) -> Result<Vec<Proof>, DataIntegrityError> {
+        self.verify_proof_inner(document, Some(did), purpose, OffsetDateTime::now_utc())
+            .await
+    }
+
+    async fn verify_proof_inner(
+        &self,
+        document: &(impl Serialize + Sync),
+        did: Option<&Did>,
+        purpose: ProofPurpose,
+        now: OffsetDateTime,
+    ) -> Result<Vec<Proof>, DataIntegrityError> {
+        let mut document = to_object(document)?;
+
+        let proofs = match document.remove("proof") {
+            Some(Value::Array(proofs)) => proofs,
+            Some(proof) => vec![proof],
+            None => Vec::new(),
+        };
+        if proofs.is_empty() {
+            return Err(DataIntegrityError::MissingProof);
+        }
+
+        let mut verified = Vec::with_capacity(proofs.len());
+        for proof in proofs {
+            verified.push(
+                self.verify_single(&document, proof, did, purpose, now)
+                    .await?,
+            );
+        }
+
+        Ok(verified)
+    }
+
+    async fn verify_single(
+        &self,
+        document: &Map<String, Value>,
+        config: Value,
+        did: Option<&Did>,
+        purpose: ProofPurpose,
+        now: OffsetDateTime,
+    ) -> Result<Proof, DataIntegrityError> {
+        let proof: Proof = serde_json::from_value(config.clone())?;
+
+        // Hash the proof as received, rather than re-serializing it.
+        let Value::Object(mut config) = config else {
+            return Err(DataIntegrityError::NotAnObject);
+        };
+        config.remove("proofValue");
+
+        if proof.typ != PROOF_TYPE {
+            return Err(DataIntegrityError::InvalidProofType);
+        }
+        if proof.proof_purpose != purpose {
+            return Err(DataIntegrityError::PurposeMismatch);
+        }
+        if proof.expires.is_some_and(|expires| expires < now) {
+            return Err(DataIntegrityError::Expired);
+        }
+        if let Some(context) = &proof.context
+            && !context_starts_with(document.get("@context"), context)
+        {
+            return Err(DataIntegrityError::ContextMismatch);
+        }
+
+        let proof_value = proof
+            .proof_value
+            .as_deref()
+            .ok_or(DataIntegrityError::MissingProofValue)?;
+        let (base, signature) = multibase::decode(proof_value)?;
+        if base != Base::Base58Btc {
+            return Err(DataIntegrityError::InvalidProofValue);
+        }
+
+        let url = &proof.verification_method;
+        let key = self
+            .resolve_public_key_for(did.unwrap_or(&url.did), url, purpose.into())
+            .await?;
+        let alg = key
+            .jws_alg()
+            .filter(|alg| cryptosuite(*alg) == Some(proof.cryptosuite.as_str()))
+            .ok_or_else(|| DataIntegrityError::UnsupportedCryptosuite(proof.cryptosuite.clone()))?;
+
+        if signature_len(alg) != Some(signature.len()) {
+            return Err(DataIntegrityError::InvalidProofValue);
+        }
+
+        let hash_data = hash_data(alg, &config, document)?;
+        key.verify_fixed(&hash_data, &signature)
+            .map_err(VerifyError::InvalidSignature)?;
+
+        Ok(proof)
+    }
+}
+
+/// The JCS cryptosuite for a signing algorithm.
+const fn cryptosuite(alg: Signing) -> Option<&'static str> {
+    match alg {
+        Signing::EdDsa => Some(EDDSA_JCS_2022),
+        Signing::Es256 | Signing::Es384 => Some(ECDSA_JCS_2019),
+        _ => None,
+    }
+}
+
+/// Concatenated hashes of the canonical proof configuration and document.
+/// P-384 keys use SHA-384, all others SHA-256.
+fn hash_data(
+    alg: Signing,
+    config: &Map<String, Value>,
+    document: &Map<String, Value>,
+) -> Result<Vec<u8>, DataIntegrityError> {
+    let config = jcs::to_vec(config)?;
+    let document = jcs::to_vec(document)?;
+
+    let mut data = Vec::new();
+    if alg == Signing::Es384 {
+        data.extend(Sha384::digest(config));
+        data.extend(Sha384::digest(document));
+    } else {
+        data.extend(Sha256::digest(config));
+        data.extend(Sha256::digest(document));
+    }
+
+    Ok(data)
+}
+
+fn to_object(value: &impl Serialize) -> Result<Map<String, Value>, DataIntegrityError> {
+    match serde_json::to_value(value)? {
+        Value::Object(map) => Ok(map),
+        _ => Err(DataIntegrityError::NotAnObject),
+    }
+}
+
+/// Whether a document's `@context` begins with every value of a proof's
+/// `@context`, in order.
+fn context_starts_with(document: Option<&Value>, proof: &Value) -> bool {
+    fn as_slice(value: &Value) -> &[Value] {
+        match value {
+            Value::Array(values) => values,
+            value => std::slice::from_ref(value),
+        }
+    }
+
+    document.is_some_and(|document| as_slice(document).starts_with(as_slice(proof)))
+}
+
+#[derive(Error, Debug)]
+pub enum DataIntegrityError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("canonicalization failed: {0}")]
+    Canonicalize(#[from] JcsError),
+    #[error("document is not a JSON object")]
+    NotAnObject,
+    #[error("key has no supported cryptosuite")]
+    UnsupportedKey,
+    #[error("unsupported cryptosuite: {0}")]
+    UnsupportedCryptosuite(String),
+    #[error("proof type is not {PROOF_TYPE}")]
+    InvalidProofType,
+    #[error("missing proof")]
+    MissingProof,
+    #[error("missing proofValue")]
+    MissingProofValue,
+    #[error("invalid multibase: {0}")]
+    Multibase(#[from] multibase::Error),
+    #[error("proofValue is not a base58btc fixed-size signature")]
+    InvalidProofValue,
+    #[error("proofPurpose does not match")]
+    PurposeMismatch,
+    #[error("proof expired")]
+    Expired,
+    #[error("proof @context does not match document")]
+    ContextMismatch,
+    #[error("signing failed: {0}")]
+    Signing(anyhow::Error),
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use xdid_method_key::{
+        ed25519::Ed25519KeyPair,
+        p256::P256KeyPair,
+        p384::P384KeyPair,
+        secp256k1::Secp256k1KeyPair,
+    };
+
+    use super::*;
+
+    fn document() -> Value {
+        json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:issuer",
+            "credentialSubject": { "name": "Alice" },
+        })
+    }
+
+    async fn verify(
+        document: &Value,
+        purpose: ProofPurpose,
+    ) -> Result<Vec<Proof>, DataIntegrityError> {
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver.verify_proof(document, purpose).await
+    }
+
+    async fn roundtrip(pair: &(impl DidKeyPair + Sync), cryptosuite: &str) {
+        let secured = add_proof(pair, &document(), &ProofOptions::default())
+            .expect("proof creation should succeed");
+
+        let proofs = verify(&secured, ProofPurpose::AssertionMethod)
+            .await
+            .expect("verification should succeed");
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(proofs[0].cryptosuite, cryptosuite);
+        assert_eq!(proofs[0].context, document().get("@context").cloned());
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_ed25519() {
+        roundtrip(&Ed25519KeyPair::generate(), EDDSA_JCS_2022).await;
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_p256() {
+        roundtrip(&P256KeyPair::generate(), ECDSA_JCS_2019).await;
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_p384() {
+        roundtrip(&P384KeyPair::generate(), ECDSA_JCS_2019).await;
+    }
+
+    #[test]
+    fn test_unsupported_key() {
+        let res = create_proof(
+            &Secp256k1KeyPair::generate(),
+            &document(),
+            &ProofOptions::default(),
+        );
+        assert!(matches!(res, Err(DataIntegrityError::UnsupportedKey)));
+    }
+
+    #[tokio::test]
+    async fn test_tampered() {
+        let pair = Ed25519KeyPair::generate();
+        let mut secured = add_proof(&pair, &document(), &ProofOptions::default())
+            .expect("proof creation should succeed");
+        secured["credentialSubject"]["name"] = json!("Mallory");
+
+        let res = verify(&secured, ProofPurpose::AssertionMethod).await;
+        assert!(matches!(
+            res,
+            Err(DataIntegrityError::Verify(VerifyError::InvalidSignature(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purpose() {
+        let pair = P256KeyPair::generate();
+        let secured = add_proof(&pair, &document(), &ProofOptions::default())
+            .expect("proof creation should succeed");
+
+        let res = verify(&secured, ProofPurpose::Authentication).await;
+        assert!(matches!(res, Err(DataIntegrityError::PurposeMismatch)));
+
+        let options = ProofOptions {
+            purpose: ProofPurpose::KeyAgreement,
+            ..Default::default()
+        };
+        let secured =
+            add_proof(&pair, &document(), &options).expect("proof creation should succeed");
+        let res = verify(&secured, ProofPurpose::KeyAgreement).await;
+        assert!(matches!(
+            res,
+            Err(DataIntegrityError::Verify(VerifyError::MethodNotFound))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let expires = OffsetDateTime::now_utc() + time::Duration::hours(1);
+        let options = ProofOptions {
+            expires: Some(expires),
+            ..Default::default()
+        };
+        let secured = add_proof(&Ed25519KeyPair::generate(), &document(), &options)
+            .expect("proof creation should succeed");
+
+        verify(&secured, ProofPurpose::AssertionMethod)
+            .await
+            .expect("verification should succeed before expiry");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify_proof_at(
+                &secured,
+                ProofPurpose::AssertionMethod,
+                expires + time::Duration::seconds(1),
+            )
+            .await;
+        assert!(matches!(res, Err(DataIntegrityError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_controller() {
+        let pair = Ed25519KeyPair::generate();
+        let secured = add_proof(&pair, &document(), &ProofOptions::default())
+            .expect("proof creation should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver
+            .verify_proof_for(
+                &secured,
+                &pair.public().to_did(),
+                ProofPurpose::AssertionMethod,
+            )
+            .await
+            .expect("verification should succeed");
+
+        let other = Ed25519KeyPair::generate().public().to_did();
+        let res = resolver
+            .verify_proof_for(&secured, &other, ProofPurpose::AssertionMethod)
+            .await;
+        assert!(matches!(
+            res,
+            Err(DataIntegrityError::Verify(VerifyError::MethodNotFound))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_context_mismatch() {
+        let pair = Ed25519KeyPair::generate();
+        let mut secured = add_proof(&pair, &document(), &ProofOptions::default())
+            .expect("proof creation should succeed");
+        secured["@context"] = json!(["https://www.w3.org/2018/credentials/v1"]);
+
+        let res = verify(&secured, ProofPurpose::AssertionMethod).await;
+        assert!(matches!(res, Err(DataIntegrityError::ContextMismatch)));
+    }
+
+    #[tokio::test]
+    async fn test_proof_set() {
+        let options = ProofOptions {
+            created: Some(OffsetDateTime::now_utc()),
+            ..Default::default()
+        };
+        let secured = add_proof(&Ed25519KeyPair::generate(), &document(), &options)
+            .expect("proof creation should succeed");
+        let secured = add_proof(&P256KeyPair::generate(), &secured, &options)
+            .expect("proof creation should succeed");
+
+        let proofs = verify(&secured, ProofPurpose::AssertionMethod)
+            .await
+            .expect("verification should succeed");
+        assert_eq!(proofs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_did_document() {
+        let pair = Ed25519KeyPair::generate();
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let document = resolver
+            .resolve(&pair.public().to_did())
+            .await
+            .expect("resolution should succeed");
+
+        let options = ProofOptions {
+            purpose: ProofPurpose::Authentication,
+            ..Default::default()
+        };
+        let secured = add_proof(&pair, &document, &options).expect("proof creation should succeed");
+
+        verify(&secured, ProofPurpose::Authentication)
+            .await
+            .expect("verification should succeed");
+    }
+}
@@ -0,0 +1,357 @@
+//! Compact [JWS](https://www.rfc-editor.org/rfc/rfc7515) signing and
+//! verification with DID keys.
+
+use base64::{
+    Engine,
+    engine::general_purpose::URL_SAFE_NO_PAD,
+};
+use jose_jwk::jose_jwa::Signing;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+use xdid_core::{
+    did::Did,
+    did_url::DidUrl,
+    document::VerificationRole,
+};
+use xdid_method_key::{
+    DidKeyPair,
+    PublicKey,
+    Signer,
+};
+
+use crate::{
+    resolver::DidResolver,
+    verify::VerifyError,
+};
+
+/// JOSE header of a JWS.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub alg:  Signing,
+    /// DID URL of the verification method that signed the JWS.
+    pub kid:  Option<DidUrl>,
+    pub typ:  Option<String>,
+    /// Critical extension headers. None are understood, so any JWS that has
+    /// them is rejected.
+    pub crit: Option<Vec<String>>,
+}
+
+/// A verified JWS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Jws {
+    pub header:  Header,
+    pub payload: Vec<u8>,
+}
+
+/// Sign a payload as a compact JWS. The `kid` is set to the key pair's
+/// did:key verification method.
+///
+/// # Errors
+///
+/// Returns an error if the key cannot be used for JWS, or signing fails.
+pub fn sign(pair: &impl DidKeyPair, payload: &[u8]) -> Result<String, JwsError> {
+    let header = header(pair, None)?;
+    sign_with_header(pair, &header, payload)
+}
+
+/// Builds the header for a did:key key pair, with `kid` set to its
+/// verification method.
+pub(crate) fn header(pair: &impl DidKeyPair, typ: Option<&str>) -> Result<Header, JwsError> {
+    let public = pair.public();
+    let alg = public.jws_alg().ok_or(JwsError::UnsupportedKey)?;
+    let did = public.to_did();
+
+    Ok(Header {
+        alg,
+        kid: Some(DidUrl {
+            fragment: Some(did.method_id.0.clone().into()),
+            did,
+            path_abempty: None,
+            query: None,
+        }),
+        typ: typ.map(Into::into),
+        crit: None,
+    })
+}
+
+/// Sign a payload as a compact JWS, using a custom header.
+///
+/// # Errors
+///
+/// Returns an error if signing fails.
+pub fn sign_with_header(
+    signer: &impl Signer,
+    header: &Header,
+    payload: &[u8],
+) -> Result<String, JwsError> {
+    let header_json = serde_json::to_vec(header)?;
+
+    let mut token = URL_SAFE_NO_PAD.encode(header_json);
+    token.push('.');
+    token.push_str(&URL_SAFE_NO_PAD.encode(payload));
+
+    let signature = signer
+        .sign_fixed(token.as_bytes())
+        .map_err(JwsError::Signing)?;
+    if signature_len(header.alg) != Some(signature.len()) {
+        return Err(JwsError::UnsupportedKey);
+    }
+
+    token.push('.');
+    token.push_str(&URL_SAFE_NO_PAD.encode(signature));
+
+    Ok(token)
+}
+
+impl DidResolver {
+    /// Verify a compact JWS. The `kid` header is resolved to a verification
+    /// method, which must be listed under the given [`VerificationRole`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JWS is malformed, the key cannot be resolved,
+    /// or the signature is invalid.
+    pub async fn verify_jws(&self, token: &str, role: VerificationRole) -> Result<Jws, JwsError> {
+        self.verify_jws_inner(token, None, role).await
+    }
+
+    /// Verify a compact JWS signed on behalf of `did`. The `kid` header must
+    /// be listed under the given [`VerificationRole`] of `did`'s document, and
+    /// may reference a key delegated from another DID document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JWS is malformed, the key cannot be resolved
+    /// or is not authorized, or the signature is invalid.
+    pub async fn verify_jws_for(
+        &self,
+        token: &str,
+        did: &Did,
+        role: VerificationRole,
+    ) -> Result<Jws, JwsError> {
+        self.verify_jws_inner(token, Some(did), role).await
+    }
+
+    async fn verify_jws_inner(
+        &self,
+        token: &str,
+        did: Option<&Did>,
+        role: VerificationRole,
+    ) -> Result<Jws, JwsError> {
+        let mut parts = token.split('.');
+        let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(JwsError::Malformed);
+        };
+
+        let header: Header = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header_b64)?)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload_b64)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature_b64)?;
+
+        if header.crit.is_some() {
+            return Err(JwsError::UnsupportedCritical);
+        }
+
+        let kid = header.kid.as_ref().ok_or(JwsError::MissingKid)?;
+        let key = self
+            .resolve_public_key_for(did.unwrap_or(&kid.did), kid, role)
+            .await?;
+
+        if key.jws_alg() != Some(header.alg) {
+            return Err(JwsError::AlgorithmMismatch);
+        }
+
+        // ECDSA signatures must be `r || s`, not DER, so they are not
+        // malleable.
+        if signature_len(header.alg) != Some(signature.len()) {
+            return Err(JwsError::InvalidSignatureLength);
+        }
+
+        let signing_input = &token[..header_b64.len() + 1 + payload_b64.len()];
+        key.verify_fixed(signing_input.as_bytes(), &signature)
+            .map_err(VerifyError::InvalidSignature)?;
+
+        Ok(Jws { header, payload })
+    }
+}
+
+/// Decodes the header and payload of a compact JWS, without verifying it.
+pub(crate) fn decode(token: &str) -> Result<Jws, JwsError> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(payload_b64)) = (parts.next(), parts.next()) else {
+        return Err(JwsError::Malformed);
+    };
+
+    Ok(Jws {
+        header:  serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header_b64)?)?,
+        payload: URL_SAFE_NO_PAD.decode(payload_b64)?,
+    })
+}
+
+/// Length of a fixed-size signature for an algorithm.
+pub(crate) const fn signature_len(alg: Signing) -> Option<usize> {
+    match alg {
+        Signing::EdDsa | Signing::Es256 | Signing::Es256K => Some(64),
+        Signing::Es384 => Some(96),
+        Signing::Es512 => Some(132),
+        _ => None,
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum JwsError {
+    #[error("malformed JWS")]
+    Malformed,
+    #[error("invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("missing kid header")]
+    MissingKid,
+    #[error("key does not support JWS")]
+    UnsupportedKey,
+    #[error("alg header does not match verification method")]
+    AlgorithmMismatch,
+    #[error("unsupported crit header")]
+    UnsupportedCritical,
+    #[error("signature length does not match alg")]
+    InvalidSignatureLength,
+    #[error("signing failed: {0}")]
+    Signing(anyhow::Error),
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+}
+
+#[cfg(test)]
+mod tests {
+    use xdid_method_key::{
+        ed25519::Ed25519KeyPair,
+        p256::P256KeyPair,
+        p384::P384KeyPair,
+        p521::P521KeyPair,
+        secp256k1::Secp256k1KeyPair,
+    };
+
+    use super::*;
+
+    async fn roundtrip(pair: &(impl DidKeyPair + Sync), alg: Signing) {
+        let token = sign(pair, b"hello").expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let jws = resolver
+            .verify_jws(&token, VerificationRole::Authentication)
+            .await
+            .expect("verification should succeed");
+
+        assert_eq!(jws.header.alg, alg);
+        assert_eq!(jws.payload, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        roundtrip(&Ed25519KeyPair::generate(), Signing::EdDsa).await;
+        roundtrip(&P256KeyPair::generate(), Signing::Es256).await;
+        roundtrip(&P384KeyPair::generate(), Signing::Es384).await;
+        roundtrip(&P521KeyPair::generate(), Signing::Es512).await;
+        roundtrip(&Secp256k1KeyPair::generate(), Signing::Es256K).await;
+    }
+
+    #[test]
+    fn test_header() {
+        let pair = P256KeyPair::generate();
+        let token = sign(&pair, b"hello").expect("signing should succeed");
+
+        let header_b64 = token.split('.').next().expect("header");
+        let header: Header =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header_b64).expect("valid base64"))
+                .expect("valid header");
+
+        let did = pair.public().to_did();
+        let kid = header.kid.expect("kid");
+        assert_eq!(kid.did, did);
+        assert_eq!(kid.fragment.as_deref(), Some(did.method_id.0.as_str()));
+    }
+
+    #[test]
+    fn test_fixed_size_signature() {
+        let pair = P256KeyPair::generate();
+        let token = sign(&pair, b"hello").expect("signing should succeed");
+
+        let signature_b64 = token.split('.').nth(2).expect("signature");
+        let signature = URL_SAFE_NO_PAD.decode(signature_b64).expect("valid base64");
+        assert_eq!(signature.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_payload() {
+        let pair = P256KeyPair::generate();
+        let token = sign(&pair, b"hello").expect("signing should succeed");
+
+        let mut parts: Vec<_> = token.split('.').collect();
+        let payload = URL_SAFE_NO_PAD.encode(b"bye");
+        parts[1] = &payload;
+        let tampered = parts.join(".");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify_jws(&tampered, VerificationRole::Authentication)
+            .await;
+        assert!(matches!(
+            res,
+            Err(JwsError::Verify(VerifyError::InvalidSignature(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_alg_mismatch() {
+        let pair = P256KeyPair::generate();
+        let mut header = header(&pair, None).expect("valid header");
+        header.alg = Signing::EdDsa;
+        let token = sign_with_header(&pair, &header, b"hello").expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify_jws(&token, VerificationRole::Authentication)
+            .await;
+        assert!(matches!(res, Err(JwsError::AlgorithmMismatch)));
+    }
+
+    #[tokio::test]
+    async fn test_der_signature() {
+        let pair = P256KeyPair::generate();
+        let token = sign(&pair, b"hello").expect("signing should succeed");
+
+        let (signing_input, _) = token.rsplit_once('.').expect("signature");
+        let der = pair
+            .sign(signing_input.as_bytes())
+            .expect("signing should succeed");
+        assert_ne!(der.len(), 64);
+        let der_token = format!("{signing_input}.{}", URL_SAFE_NO_PAD.encode(der));
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify_jws(&der_token, VerificationRole::Authentication)
+            .await;
+        assert!(matches!(res, Err(JwsError::InvalidSignatureLength)));
+    }
+
+    #[tokio::test]
+    async fn test_crit() {
+        let pair = P256KeyPair::generate();
+        let mut header = header(&pair, None).expect("valid header");
+        header.crit = Some(vec!["exp".to_string()]);
+        let token = sign_with_header(&pair, &header, b"hello").expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify_jws(&token, VerificationRole::Authentication)
+            .await;
+        assert!(matches!(res, Err(JwsError::UnsupportedCritical)));
+    }
+}
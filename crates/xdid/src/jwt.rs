@@ -0,0 +1,456 @@
+//! [JWT](https://www.rfc-editor.org/rfc/rfc7519) issuance and validation with
+//! DID issuers.
+
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
+
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+    de::{
+        self,
+        DeserializeOwned,
+    },
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use serde_with::{
+    DeserializeAs,
+    OneOrMany,
+    SerializeAs,
+    serde_as,
+    skip_serializing_none,
+};
+use thiserror::Error;
+use xdid_core::{
+    did::Did,
+    document::VerificationRole,
+};
+use xdid_method_key::DidKeyPair;
+
+use crate::{
+    jws::{
+        self,
+        JwsError,
+    },
+    resolver::DidResolver,
+};
+
+/// JWT claims set. Registered claims are typed, with `iss` and `sub` as DIDs,
+/// and any other claims are stored in `custom`.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Claims<T = Map<String, Value>> {
+    pub iss:    Option<Did>,
+    pub sub:    Option<Did>,
+    #[serde_as(as = "Option<OneOrMany<_>>")]
+    #[serde(default)]
+    pub aud:    Option<Vec<String>>,
+    /// Expiration time, in seconds since the Unix epoch.
+    #[serde_as(as = "Option<NumericDate>")]
+    #[serde(default)]
+    pub exp:    Option<u64>,
+    /// Not before time, in seconds since the Unix epoch.
+    #[serde_as(as = "Option<NumericDate>")]
+    #[serde(default)]
+    pub nbf:    Option<u64>,
+    /// Issued at time, in seconds since the Unix epoch.
+    #[serde_as(as = "Option<NumericDate>")]
+    #[serde(default)]
+    pub iat:    Option<u64>,
+    pub jti:    Option<String>,
+    #[serde(flatten)]
+    pub custom: T,
+}
+
+/// A [NumericDate](https://www.rfc-editor.org/rfc/rfc7519#section-2), which
+/// may have a fractional part. Fractional seconds are truncated.
+struct NumericDate;
+
+impl SerializeAs<u64> for NumericDate {
+    fn serialize_as<S: Serializer>(source: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(*source)
+    }
+}
+
+impl<'de> DeserializeAs<'de, u64> for NumericDate {
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        match serde_json::Number::deserialize(deserializer)? {
+            n if n.is_u64() => n
+                .as_u64()
+                .ok_or_else(|| de::Error::custom("invalid NumericDate")),
+            n => match n.as_f64() {
+                Some(secs) if secs.is_finite() && secs >= 0.0 => Ok(secs.floor() as u64),
+                _ => Err(de::Error::custom("invalid NumericDate")),
+            },
+        }
+    }
+}
+
+impl<T: Default> Claims<T> {
+    /// Creates a claims set issued by `iss` at the current time.
+    #[must_use]
+    pub fn new(iss: Did) -> Self {
+        Self {
+            iss:    Some(iss),
+            sub:    None,
+            aud:    None,
+            exp:    None,
+            nbf:    None,
+            iat:    Some(unix_time(SystemTime::now())),
+            jti:    None,
+            custom: T::default(),
+        }
+    }
+}
+
+/// Sign a claims set as a JWT.
+///
+/// # Errors
+///
+/// Returns an error if the key cannot be used for JWS, or signing fails.
+pub fn issue<T: Serialize>(pair: &impl DidKeyPair, claims: &Claims<T>) -> Result<String, JwtError> {
+    let header = jws::header(pair, Some("JWT"))?;
+    let payload = serde_json::to_vec(claims)?;
+    Ok(jws::sign_with_header(pair, &header, &payload)?)
+}
+
+#[derive(Deserialize)]
+struct Issuer {
+    iss: Option<Did>,
+}
+
+/// Options for [`DidResolver::verify_jwt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    /// Role the issuer's key must be listed under.
+    pub role:     VerificationRole,
+    /// Expected audience. If set, the JWT must have an `aud` claim that
+    /// contains this value. If not set, a JWT with an `aud` claim is rejected,
+    /// as it is intended for a specific audience.
+    pub audience: Option<String>,
+    /// Allowed clock skew for time-based claims.
+    pub leeway:   Duration,
+    /// Time to validate against. Defaults to the system time.
+    pub now:      Option<SystemTime>,
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Self {
+            role:     VerificationRole::Assertion,
+            audience: None,
+            leeway:   Duration::from_mins(1),
+            now:      None,
+        }
+    }
+}
+
+impl DidResolver {
+    /// Verify a JWT and validate its claims. The signing key must be a
+    /// verification method of the `iss` DID, or delegated to it from another
+    /// DID document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is invalid, or any claim fails
+    /// validation.
+    pub async fn verify_jwt<T: DeserializeOwned>(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<Claims<T>, JwtError> {
+        // The issuer is read before verification, to find the signing key.
+        let unverified: Issuer = serde_json::from_slice(&jws::decode(token)?.payload)?;
+        let iss = unverified.iss.ok_or(JwtError::MissingIssuer)?;
+
+        let jws = self.verify_jws_for(token, &iss, validation.role).await?;
+        let claims: Claims<T> = serde_json::from_slice(&jws.payload)?;
+        claims.validate(validation)?;
+
+        Ok(claims)
+    }
+}
+
+impl<T> Claims<T> {
+    /// Validates the time-based claims and audience. The signature is not
+    /// checked.
+    pub(crate) fn validate(&self, validation: &Validation) -> Result<(), JwtError> {
+        let now = unix_time(validation.now.unwrap_or_else(SystemTime::now));
+        let leeway = validation.leeway.as_secs();
+
+        if let Some(exp) = self.exp
+            && now >= exp.saturating_add(leeway)
+        {
+            return Err(JwtError::Expired);
+        }
+
+        if let Some(nbf) = self.nbf
+            && now.saturating_add(leeway) < nbf
+        {
+            return Err(JwtError::NotYetValid);
+        }
+
+        if let Some(iat) = self.iat
+            && now.saturating_add(leeway) < iat
+        {
+            return Err(JwtError::IssuedInFuture);
+        }
+
+        if let Some(aud) = &self.aud {
+            let matches = validation
+                .audience
+                .as_ref()
+                .is_some_and(|expected| aud.contains(expected));
+            if !matches {
+                return Err(JwtError::InvalidAudience);
+            }
+        } else if validation.audience.is_some() {
+            return Err(JwtError::InvalidAudience);
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[derive(Error, Debug)]
+pub enum JwtError {
+    #[error(transparent)]
+    Jws(#[from] JwsError),
+    #[error("invalid claims: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("missing iss claim")]
+    MissingIssuer,
+    #[error("token expired")]
+    Expired,
+    #[error("token not yet valid")]
+    NotYetValid,
+    #[error("token issued in the future")]
+    IssuedInFuture,
+    #[error("audience does not match")]
+    InvalidAudience,
+}
+
+#[cfg(test)]
+mod tests {
+    use xdid_method_key::{
+        PublicKey,
+        p256::P256KeyPair,
+    };
+
+    use super::*;
+    use crate::{
+        test_utils::MethodMock,
+        verify::VerifyError,
+    };
+
+    fn claims(pair: &P256KeyPair) -> Claims {
+        let mut claims = Claims::new(pair.public().to_did());
+        claims.sub = Some(P256KeyPair::generate().public().to_did());
+        claims.exp = claims.iat.map(|iat| iat + 600);
+        claims
+    }
+
+    async fn verify(token: &str, validation: &Validation) -> Result<Claims, JwtError> {
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver.verify_jwt(token, validation).await
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        let pair = P256KeyPair::generate();
+        let mut claims = claims(&pair);
+        claims
+            .custom
+            .insert("name".to_string(), Value::String("alice".to_string()));
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+        let verified = verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+        assert_eq!(verified, claims);
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let pair = P256KeyPair::generate();
+        let mut claims = claims(&pair);
+        claims.exp = Some(1000);
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(JwtError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_expiry_boundary() {
+        let pair = P256KeyPair::generate();
+        let claims = claims(&pair);
+        let exp = claims.exp.expect("exp");
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+        let validation = |now: u64| Validation {
+            leeway: Duration::ZERO,
+            now: Some(UNIX_EPOCH + Duration::from_secs(now)),
+            ..Default::default()
+        };
+
+        verify(&token, &validation(exp - 1))
+            .await
+            .expect("verification should succeed before exp");
+        let res = verify(&token, &validation(exp)).await;
+        assert!(matches!(res, Err(JwtError::Expired)));
+    }
+
+    #[test]
+    fn test_fractional_numeric_date() {
+        let claims: Claims = serde_json::from_value(serde_json::json!({
+            "iss": "did:example:issuer",
+            "exp": 1_700_000_000.5,
+            "nbf": 1_600_000_000,
+            "iat": 1_600_000_000.999,
+        }))
+        .expect("claims should deserialize");
+        assert_eq!(claims.exp, Some(1_700_000_000));
+        assert_eq!(claims.nbf, Some(1_600_000_000));
+        assert_eq!(claims.iat, Some(1_600_000_000));
+
+        let res = serde_json::from_value::<Claims>(serde_json::json!({ "exp": -1.5 }));
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_leeway() {
+        let pair = P256KeyPair::generate();
+        let mut claims = claims(&pair);
+        let iat = claims.iat.expect("iat");
+        claims.nbf = Some(iat + 30);
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+        verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed within leeway");
+
+        let validation = Validation {
+            leeway: Duration::ZERO,
+            ..Default::default()
+        };
+        let res = verify(&token, &validation).await;
+        assert!(matches!(res, Err(JwtError::NotYetValid)));
+    }
+
+    #[tokio::test]
+    async fn test_audience() {
+        let pair = P256KeyPair::generate();
+        let mut claims = claims(&pair);
+        claims.aud = Some(vec!["https://a.example".to_string()]);
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(JwtError::InvalidAudience)));
+
+        let validation = Validation {
+            audience: Some("https://b.example".to_string()),
+            ..Default::default()
+        };
+        let res = verify(&token, &validation).await;
+        assert!(matches!(res, Err(JwtError::InvalidAudience)));
+
+        let validation = Validation {
+            audience: Some("https://a.example".to_string()),
+            ..Default::default()
+        };
+        verify(&token, &validation)
+            .await
+            .expect("verification should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_issuer_mismatch() {
+        let pair = P256KeyPair::generate();
+        let mut claims = claims(&pair);
+        claims.iss = Some(P256KeyPair::generate().public().to_did());
+
+        let token = issue(&pair, &claims).expect("issue should succeed");
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(
+            res,
+            Err(JwtError::Jws(JwsError::Verify(VerifyError::MethodNotFound)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_role() {
+        let pair = P256KeyPair::generate();
+        let token = issue(&pair, &claims(&pair)).expect("issue should succeed");
+
+        let validation = Validation {
+            role: VerificationRole::KeyAgreement,
+            ..Default::default()
+        };
+        let res = verify(&token, &validation).await;
+        assert!(matches!(res, Err(JwtError::Jws(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delegated_key() {
+        use xdid_core::document::Document;
+
+        // The issuer delegates assertion to a did:key that it lists as a
+        // controller.
+        let pair = P256KeyPair::generate();
+        let key_did = pair.public().to_did();
+        let kid = format!("{key_did}#{}", key_did.method_id.0);
+        let issuer: Document = serde_json::from_value(serde_json::json!({
+            "id": "did:example:issuer",
+            "controller": [key_did],
+            "assertionMethod": [kid],
+        }))
+        .expect("valid document");
+
+        let mut claims = Claims::<Map<String, Value>>::new(issuer.id.clone());
+        claims.exp = claims.iat.map(|iat| iat + 600);
+        let token = issue(&pair, &claims).expect("issue should succeed");
+
+        let resolver = DidResolver::builder()
+            .with_method(MethodMock::new("example").with_documents([issuer.clone()]))
+            .build()
+            .expect("resolver construction should succeed");
+        let verified = resolver
+            .verify_jwt::<Map<String, Value>>(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+        assert_eq!(verified, claims);
+
+        // Without the controller, the delegation is not authorized.
+        let mut unauthorized = issuer;
+        unauthorized.controller = None;
+        let resolver = DidResolver::builder()
+            .with_method(MethodMock::new("example").with_documents([unauthorized]))
+            .build()
+            .expect("resolver construction should succeed");
+        let res = resolver
+            .verify_jwt::<Map<String, Value>>(&token, &Validation::default())
+            .await;
+        assert!(matches!(
+            res,
+            Err(JwtError::Jws(JwsError::Verify(VerifyError::Reference(_))))
+        ));
+    }
+}
@@ -28,7 +28,16 @@
 //! }
 //! ```
 
+#[cfg(feature = "cache")] pub mod cache;
+#[cfg(feature = "data-integrity")] pub mod data_integrity;
+#[cfg(feature = "jose")] pub mod jws;
+#[cfg(feature = "jose")] pub mod jwt;
+#[cfg(feature = "offline")] pub mod offline;
 pub mod resolver;
+#[cfg(test)] mod test_utils;
+#[cfg(feature = "vc")] pub mod vc;
+pub mod verification_method;
+#[cfg(feature = "did-key")] pub mod verify;
 
 pub mod core {
     pub use xdid_core::*;
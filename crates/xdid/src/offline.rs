@@ -0,0 +1,301 @@
+//! Offline resolution of DIDs from local documents, for tests and air-gapped
+//! deployments.
+//!
+//! ```
+//! # use std::str::FromStr;
+//! use xdid::{
+//!     core::{
+//!         did::Did,
+//!         document::Document,
+//!     },
+//!     offline::MethodOffline,
+//!     resolver::DidResolver,
+//! };
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let did = Did::from_str("did:web:example.com").unwrap();
+//! # let document = Document::new(did.clone());
+//!
+//! // Stand in for did:web.
+//! let method = MethodOffline::new("web").with_document(document);
+//! let resolver = DidResolver {
+//!     methods: vec![Box::new(method) as _].into(),
+//! };
+//!
+//! assert_eq!(resolver.resolve(&did).await.unwrap().id, did);
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+};
+
+use thiserror::Error;
+use xdid_core::{
+    Method,
+    MethodFuture,
+    ResolutionError,
+    did::Did,
+    document::Document,
+    resolution::{
+        ResolutionOptions,
+        ResolutionResult,
+    },
+};
+
+/// A [`Method`] that serves [`Document`]s from memory, keyed by their `id`.
+///
+/// It can stand in for a method by name, or be placed in front of another
+/// method, which resolves any DID that is not stored locally.
+pub struct MethodOffline {
+    name:      &'static str,
+    documents: Arc<HashMap<Did, Document>>,
+    fallback:  Option<Box<dyn Method>>,
+}
+
+impl MethodOffline {
+    /// Creates an empty method with the given name, such as `"web"`.
+    #[must_use]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            documents: Arc::default(),
+            fallback: None,
+        }
+    }
+
+    /// Creates an empty method in front of `method`, using its name.
+    #[must_use]
+    pub fn with_fallback(method: impl Method + 'static) -> Self {
+        Self {
+            name:      method.method_name(),
+            documents: Arc::default(),
+            fallback:  Some(Box::new(method)),
+        }
+    }
+
+    /// Adds a document, keyed by its `id`.
+    #[must_use]
+    pub fn with_document(mut self, document: Document) -> Self {
+        self.insert(document);
+        self
+    }
+
+    /// Adds a document, keyed by its `id`, replacing any previous document.
+    pub fn insert(&mut self, document: Document) {
+        Arc::make_mut(&mut self.documents).insert(document.id.clone(), document);
+    }
+
+    /// Adds every `.json` file in a directory as a document, keyed by its
+    /// `id`. Documents of other methods are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, or a file is not a
+    /// valid document.
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Result<(), OfflineError> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+
+            let data = fs::read(&path)?;
+            let document = serde_json::from_slice::<Document>(&data)
+                .map_err(|source| OfflineError::Json { path, source })?;
+
+            if document.id.method_name.0 == self.name {
+                self.insert(document);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds every `.json` file in a directory as a document, like
+    /// [`Self::load_dir`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::load_dir`].
+    pub fn with_dir(mut self, dir: impl AsRef<Path>) -> Result<Self, OfflineError> {
+        self.load_dir(dir)?;
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn get(&self, did: &Did) -> Option<&Document> {
+        self.documents.get(did)
+    }
+}
+
+impl Method for MethodOffline {
+    fn method_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
+        if let Some(document) = self.documents.get(&did) {
+            let document = document.clone();
+            return Box::pin(async move { Ok(document) });
+        }
+
+        if let Some(fallback) = &self.fallback {
+            return fallback.resolve(did);
+        }
+
+        Box::pin(async move {
+            Err(ResolutionError::not_found(format!(
+                "{did} not found offline"
+            )))
+        })
+    }
+
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        if let Some(fallback) = &self.fallback
+            && !self.documents.contains_key(&did)
+        {
+            return fallback.resolve_with_metadata(did, options);
+        }
+
+        let fut = self.resolve(did);
+        Box::pin(async move {
+            options.reject_versions()?;
+            fut.await.map(ResolutionResult::from)
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OfflineError {
+    #[error("failed to read documents: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid document {}: {source}", path.display())]
+    Json {
+        path:   PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use smallvec::SmallVec;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::resolver::DidResolver;
+
+    fn did(did: &str) -> Did {
+        Did::from_str(did).expect("valid DID")
+    }
+
+    fn resolver(method: MethodOffline) -> DidResolver {
+        DidResolver {
+            methods: SmallVec::from_vec(vec![Box::new(method) as Box<dyn Method>]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stand_in() {
+        let doc = Document::new(did("did:web:example.com"));
+        let resolver = resolver(MethodOffline::new("web").with_document(doc.clone()));
+
+        let document = resolver
+            .resolve(&doc.id)
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(document, doc);
+
+        let missing = did("did:web:missing.com");
+        let res = resolver.resolve(&missing).await;
+        assert!(matches!(res, Err(ResolutionError::NotFound(_))));
+
+        let options = ResolutionOptions {
+            version_id: Some("1".to_string()),
+            ..Default::default()
+        };
+        let res = resolver.resolve_with_options(&doc.id, &options).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidOptions(_))));
+    }
+
+    #[tokio::test]
+    async fn test_load_dir() {
+        let dir = TempDir::new().expect("temp dir should be created");
+        for (name, id) in [
+            ("a.json", "did:web:a.example.com"),
+            ("b.json", "did:web:b.example.com"),
+            ("other.json", "did:other:123"),
+        ] {
+            let data =
+                serde_json::to_vec(&Document::new(did(id))).expect("serialization should succeed");
+            fs::write(dir.path().join(name), data).expect("fixture should be written");
+        }
+        fs::write(dir.path().join("README.md"), "ignored").expect("file should be written");
+
+        let method = MethodOffline::new("web")
+            .with_dir(dir.path())
+            .expect("documents should load");
+        assert!(method.get(&did("did:web:a.example.com")).is_some());
+        assert!(method.get(&did("did:other:123")).is_none());
+
+        let resolver = resolver(method);
+        resolver
+            .resolve(&did("did:web:b.example.com"))
+            .await
+            .expect("resolution should succeed");
+
+        fs::write(dir.path().join("invalid.json"), "{}").expect("file should be written");
+        let res = MethodOffline::new("web").with_dir(dir.path());
+        assert!(matches!(res, Err(OfflineError::Json { .. })));
+    }
+
+    #[cfg(feature = "did-key")]
+    #[tokio::test]
+    async fn test_fallback() {
+        use xdid_method_key::{
+            DidKeyPair,
+            MethodDidKey,
+            PublicKey,
+            p256::P256KeyPair,
+        };
+
+        let pair = P256KeyPair::generate();
+        let did = pair.public().to_did();
+
+        let mut stored = Document::new(did.clone());
+        stored.also_known_as = Some(Vec::new());
+
+        let other = P256KeyPair::generate();
+        let method = MethodOffline::with_fallback(MethodDidKey).with_document(stored.clone());
+        assert_eq!(method.method_name(), "key");
+
+        let resolver = resolver(method);
+        let document = resolver
+            .resolve(&did)
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(document, stored);
+
+        let other_did = other.public().to_did();
+        let result = resolver
+            .resolve_with_metadata(&other_did)
+            .await
+            .expect("resolution should fall back");
+        assert_eq!(result.document.id, other_did);
+        assert!(result.document.verification_method.is_some());
+    }
+}
@@ -1,10 +1,28 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use futures_util::{
+    StreamExt,
+    stream,
+};
 use smallvec::SmallVec;
 use thiserror::Error;
 use xdid_core::{
     Method,
     ResolutionError,
+    dereferencing::{
+        DereferencingError,
+        DereferencingResult,
+    },
     did::Did,
+    did_url::DidUrl,
     document::Document,
+    resolution::{
+        ResolutionOptions,
+        ResolutionResult,
+    },
 };
 
 /// Resolves DIDs using a set of provided methods.
@@ -19,16 +37,160 @@ impl DidResolver {
     ///
     /// Returns an error if a method fails to initialize.
     pub fn new() -> Result<Self, MethodError> {
-        #[allow(unused_mut)]
+        Self::builder().build()
+    }
+
+    /// Creates a [`DidResolverBuilder`], starting from all enabled methods.
+    #[must_use]
+    pub fn builder() -> DidResolverBuilder {
+        DidResolverBuilder::default()
+    }
+}
+
+/// Builder for a [`DidResolver`] with custom methods.
+///
+/// ```
+/// # fn main() -> Result<(), xdid::resolver::MethodError> {
+/// use xdid::{
+///     methods::key::MethodDidKey,
+///     resolver::DidResolver,
+/// };
+///
+/// let resolver = DidResolver::builder()
+///     .without_defaults()
+///     .with_method(MethodDidKey)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct DidResolverBuilder {
+    methods:          Vec<Box<dyn Method>>,
+    disabled:         Vec<String>,
+    without_defaults: bool,
+    #[cfg(feature = "did-web")]
+    web:              WebOptions,
+}
+
+#[cfg(feature = "did-web")]
+struct WebOptions {
+    client:  Option<xdid_method_web::reqwest::Client>,
+    timeout: Option<std::time::Duration>,
+    retry:   xdid_method_web::RetryPolicy,
+}
+
+#[cfg(feature = "did-web")]
+impl Default for WebOptions {
+    fn default() -> Self {
+        Self {
+            client:  None,
+            timeout: Some(xdid_method_web::DEFAULT_TIMEOUT),
+            retry:   xdid_method_web::RetryPolicy::default(),
+        }
+    }
+}
+
+impl DidResolverBuilder {
+    /// Adds a method. A built-in method with the same name is replaced.
+    #[must_use]
+    pub fn with_method(mut self, method: impl Method + 'static) -> Self {
+        self.methods.push(Box::new(method));
+        self
+    }
+
+    /// Removes the method with the given name, such as `"web"`.
+    #[must_use]
+    pub fn without_method(mut self, name: impl Into<String>) -> Self {
+        self.disabled.push(name.into());
+        self
+    }
+
+    /// Removes all built-in methods, leaving only those added with
+    /// [`Self::with_method`].
+    #[must_use]
+    pub const fn without_defaults(mut self) -> Self {
+        self.without_defaults = true;
+        self
+    }
+
+    /// Sets the HTTP client used by the built-in did:web method.
+    #[cfg(feature = "did-web")]
+    #[must_use]
+    pub fn with_web_client(mut self, client: xdid_method_web::reqwest::Client) -> Self {
+        self.web.client = Some(client);
+        self
+    }
+
+    /// Sets the deadline for a did:web resolution, including retries, or
+    /// disables it with `None`. Defaults to
+    /// [`xdid_method_web::DEFAULT_TIMEOUT`].
+    #[cfg(feature = "did-web")]
+    #[must_use]
+    pub const fn with_web_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.web.timeout = timeout;
+        self
+    }
+
+    /// Sets how the built-in did:web method retries transient HTTP errors.
+    #[cfg(feature = "did-web")]
+    #[must_use]
+    pub const fn with_web_retry(mut self, retry: xdid_method_web::RetryPolicy) -> Self {
+        self.web.retry = retry;
+        self
+    }
+
+    /// Builds the resolver.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two methods were added with the same name, or a
+    /// built-in method fails to initialize.
+    pub fn build(self) -> Result<DidResolver, MethodError> {
         let mut methods = SmallVec::<[Box<dyn Method>; 2]>::new();
 
+        for method in self.methods {
+            if methods
+                .iter()
+                .any(|other| other.method_name() == method.method_name())
+            {
+                return Err(MethodError::Duplicate(method.method_name()));
+            }
+            methods.push(method);
+        }
+
+        let is_enabled = |methods: &[Box<dyn Method>], name: &str| {
+            !self.without_defaults
+                && !self.disabled.iter().any(|disabled| disabled == name)
+                && !methods.iter().any(|method| method.method_name() == name)
+        };
+
         #[cfg(feature = "did-key")]
-        methods.push(Box::new(xdid_method_key::MethodDidKey));
+        if is_enabled(&methods, xdid_method_key::MethodDidKey.method_name()) {
+            methods.push(Box::new(xdid_method_key::MethodDidKey));
+        }
 
         #[cfg(feature = "did-web")]
-        methods.push(Box::new(xdid_method_web::MethodDidWeb::new()?));
+        if is_enabled(&methods, "web") {
+            let mut method = match self.web.client {
+                Some(client) => xdid_method_web::MethodDidWeb::with_client(client),
+                None => xdid_method_web::MethodDidWeb::new()?,
+            };
+            method.timeout = self.web.timeout;
+            method.retry = self.web.retry;
+            methods.push(Box::new(method));
+        }
+
+        #[cfg(not(any(feature = "did-key", feature = "did-web")))]
+        let _ = is_enabled;
+
+        methods.retain(|method| {
+            !self
+                .disabled
+                .iter()
+                .any(|disabled| disabled == method.method_name())
+        });
 
-        Ok(Self { methods })
+        Ok(DidResolver { methods })
     }
 }
 
@@ -37,22 +199,293 @@ pub enum MethodError {
     #[cfg(feature = "did-web")]
     #[error("failed to construct did:web resolver: {0}")]
     DidWeb(#[from] xdid_method_web::reqwest::Error),
+    #[error("method registered more than once: {0}")]
+    Duplicate(&'static str),
 }
 
 impl DidResolver {
     /// Resolve a DID to its document.
     ///
+    /// A deactivated DID is an error, as its document should no longer be
+    /// used. Its last document is still available from
+    /// [`Self::resolve_with_metadata`].
+    ///
     /// # Errors
     ///
-    /// Returns an error if the DID method is unsupported or resolution fails.
+    /// Returns an error if the DID method is unsupported, resolution fails,
+    /// or the DID has been deactivated.
     pub async fn resolve(&self, did: &Did) -> Result<Document, ResolutionError> {
-        for method in &self.methods {
-            if method.method_name() == did.method_name.0 {
-                return method.resolve(did.clone()).await;
-            }
+        let result = self
+            .method(did)?
+            .resolve_with_metadata(did.clone(), ResolutionOptions::default())
+            .await?;
+
+        if result.document_metadata.is_deactivated() {
+            return Err(ResolutionError::Deactivated);
         }
 
-        Err(ResolutionError::UnsupportedMethod)
+        Ok(result.document)
+    }
+
+    /// Resolve many DIDs concurrently, with at most `limit` resolutions in
+    /// flight. Duplicate DIDs are only resolved once.
+    ///
+    /// Returns one result per input DID, in the same order. A failed
+    /// resolution does not stop the others.
+    pub async fn resolve_many(
+        &self,
+        dids: impl IntoIterator<Item = Did>,
+        limit: usize,
+    ) -> Vec<Result<Document, ResolutionError>> {
+        let dids = dids.into_iter().collect::<Vec<_>>();
+
+        let mut seen = HashSet::new();
+        let unique = dids
+            .iter()
+            .filter(|did| seen.insert(*did))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let results = stream::iter(unique)
+            .map(|did| async move {
+                let res = self.resolve(&did).await;
+                (did, res)
+            })
+            .buffer_unordered(limit.max(1))
+            .collect::<HashMap<_, _>>()
+            .await;
+
+        dids.iter().map(|did| results[did].clone()).collect()
+    }
+
+    /// Resolve a DID to its document, along with resolution and document
+    /// metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DID method is unsupported or resolution fails.
+    pub async fn resolve_with_metadata(
+        &self,
+        did: &Did,
+    ) -> Result<ResolutionResult, ResolutionError> {
+        self.resolve_with_options(did, &ResolutionOptions::default())
+            .await
+    }
+
+    /// Resolve a DID with [`ResolutionOptions`], which are passed through to
+    /// the DID method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DID method is unsupported, the `accept` option
+    /// does not allow a JSON representation, the method rejects the options,
+    /// or resolution fails.
+    pub async fn resolve_with_options(
+        &self,
+        did: &Did,
+        options: &ResolutionOptions,
+    ) -> Result<ResolutionResult, ResolutionError> {
+        let method = self.method(did)?;
+
+        if !options.accepts_json() {
+            return Err(ResolutionError::RepresentationNotSupported);
+        }
+
+        method
+            .resolve_with_metadata(did.clone(), options.clone())
+            .await
+    }
+
+    /// Dereference a DID URL to a resource. The `versionId` and
+    /// `versionTime` parameters are passed to resolution, and the resolved
+    /// document is then dereferenced with [`ResolutionResult::dereference`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resolution fails, or the DID URL does not match
+    /// any resource.
+    pub async fn dereference(
+        &self,
+        url: &DidUrl,
+    ) -> Result<DereferencingResult, DereferencingError> {
+        let options = ResolutionOptions {
+            version_id: url.query_param("versionId"),
+            version_time: url.query_param("versionTime"),
+            ..Default::default()
+        };
+
+        self.resolve_with_options(&url.did, &options)
+            .await?
+            .dereference(url)
+    }
+
+    fn method(&self, did: &Did) -> Result<&dyn Method, ResolutionError> {
+        self.methods
+            .iter()
+            .find(|method| method.method_name() == did.method_name.0)
+            .map(AsRef::as_ref)
+            .ok_or_else(|| ResolutionError::MethodNotSupported(did.method_name.0.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        time::Duration,
+    };
+
+    use xdid_core::resolution::DocumentMetadata;
+
+    use super::*;
+    use crate::test_utils::MethodMock;
+
+    /// Resolves any `did:deactivated` DID to a deactivated document.
+    fn deactivated() -> MethodMock {
+        MethodMock::new("deactivated").with_document_metadata(DocumentMetadata {
+            deactivated: Some(true),
+            version_id: Some("2".to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn resolver() -> DidResolver {
+        DidResolver {
+            methods: SmallVec::from_vec(vec![Box::new(deactivated()) as Box<dyn Method>]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_with_metadata() {
+        let did = Did::from_str("did:deactivated:123").expect("valid DID");
+        let result = resolver()
+            .resolve_with_metadata(&did)
+            .await
+            .expect("resolution should succeed");
+
+        assert_eq!(result.document.id, did);
+        assert!(result.document_metadata.is_deactivated());
+        assert_eq!(result.document_metadata.version_id.as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_deactivated() {
+        let did = Did::from_str("did:deactivated:123").expect("valid DID");
+        let res = resolver().resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::Deactivated)));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_method() {
+        let did = Did::from_str("did:unknown:123").expect("valid DID");
+        let res = resolver().resolve_with_metadata(&did).await;
+        assert!(matches!(res, Err(ResolutionError::MethodNotSupported(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_with_options() {
+        let did = Did::from_str("did:deactivated:123").expect("valid DID");
+        let resolver = resolver();
+
+        let mut options = ResolutionOptions::default();
+        options
+            .properties
+            .insert("reject".to_string(), serde_json::Value::Bool(true));
+        let res = resolver.resolve_with_options(&did, &options).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidOptions(_))));
+
+        let options = ResolutionOptions {
+            accept: Some("application/did+cbor".to_string()),
+            ..Default::default()
+        };
+        let res = resolver.resolve_with_options(&did, &options).await;
+        assert!(matches!(
+            res,
+            Err(ResolutionError::RepresentationNotSupported)
+        ));
+
+        let options = ResolutionOptions {
+            accept: Some("application/did+ld+json".to_string()),
+            ..Default::default()
+        };
+        resolver
+            .resolve_with_options(&did, &options)
+            .await
+            .expect("resolution should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_builder() {
+        let resolver = DidResolver::builder()
+            .with_method(deactivated())
+            .build()
+            .expect("resolver construction should succeed");
+
+        let did = Did::from_str("did:deactivated:123").expect("valid DID");
+        resolver
+            .resolve_with_metadata(&did)
+            .await
+            .expect("resolution should succeed");
+
+        let resolver = DidResolver::builder()
+            .without_defaults()
+            .with_method(deactivated())
+            .build()
+            .expect("resolver construction should succeed");
+        assert_eq!(resolver.methods.len(), 1);
+
+        let resolver = DidResolver::builder()
+            .with_method(deactivated())
+            .without_method("deactivated")
+            .build()
+            .expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::MethodNotSupported(_))));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_many() {
+        let mut dids = (0..10)
+            .map(|i| Did::from_str(&format!("did:tracked:{i}")).expect("valid DID"))
+            .collect::<Vec<_>>();
+
+        let method = MethodMock::new("tracked")
+            .with_documents(dids.iter().cloned().map(Document::new))
+            .with_delay(Duration::from_millis(10));
+        let resolver = DidResolver::builder()
+            .without_defaults()
+            .with_method(method.clone())
+            .build()
+            .expect("resolver construction should succeed");
+
+        dids.push(dids[3].clone());
+        dids.push(Did::from_str("did:tracked:fail").expect("valid DID"));
+        dids.push(Did::from_str("did:unknown:123").expect("valid DID"));
+
+        let results = resolver.resolve_many(dids.clone(), 3).await;
+        assert_eq!(results.len(), dids.len());
+
+        for (did, res) in dids.iter().zip(&results).take(11) {
+            let document = res.as_ref().expect("resolution should succeed");
+            assert_eq!(document.id, *did);
+        }
+        assert!(matches!(results[11], Err(ResolutionError::NotFound(_))));
+        assert!(matches!(
+            results[12],
+            Err(ResolutionError::MethodNotSupported(_))
+        ));
+
+        assert_eq!(method.resolutions(), 11);
+        assert_eq!(method.peak(), 3);
+    }
+
+    #[test]
+    fn test_builder_duplicate() {
+        let res = DidResolver::builder()
+            .with_method(deactivated())
+            .with_method(deactivated())
+            .build();
+        assert!(matches!(res, Err(MethodError::Duplicate("deactivated"))));
     }
 }
 
@@ -65,6 +498,7 @@ mod did_key_tests {
     };
 
     use super::*;
+    use crate::test_utils::MethodMock;
 
     #[tokio::test]
     async fn test_resolve_did_key() {
@@ -76,6 +510,93 @@ mod did_key_tests {
             .expect("resolution should succeed");
         assert_eq!(document.id, did);
     }
+
+    #[tokio::test]
+    async fn test_resolve_did_key_with_metadata() {
+        let did = P256KeyPair::generate().public().to_did();
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let result = resolver
+            .resolve_with_metadata(&did)
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(result.document.id, did);
+        assert!(!result.document_metadata.is_deactivated());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_did_key_version() {
+        let did = P256KeyPair::generate().public().to_did();
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let options = ResolutionOptions {
+            version_id: Some("1".to_string()),
+            ..Default::default()
+        };
+        let res = resolver.resolve_with_options(&did, &options).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidOptions(_))));
+    }
+
+    #[tokio::test]
+    async fn test_builder_override_did_key() {
+        let stub = MethodMock::new("key");
+        stub.fail_with(Some(|| ResolutionError::InvalidDid(None)));
+
+        let did = P256KeyPair::generate().public().to_did();
+
+        let resolver = DidResolver::builder()
+            .with_method(stub)
+            .build()
+            .expect("resolver construction should succeed");
+        assert_eq!(
+            resolver
+                .methods
+                .iter()
+                .filter(|method| method.method_name() == "key")
+                .count(),
+            1
+        );
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidDid(None))));
+
+        let resolver = DidResolver::builder()
+            .without_method("key")
+            .build()
+            .expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::MethodNotSupported(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dereference_did_key() {
+        use std::str::FromStr;
+
+        use xdid_core::dereferencing::Content;
+
+        let did = P256KeyPair::generate().public().to_did();
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+
+        let url = DidUrl::from_str(&format!("{did}#{}", did.method_id.0)).expect("valid DID URL");
+        let result = resolver
+            .dereference(&url)
+            .await
+            .expect("dereferencing should succeed");
+        assert!(matches!(
+            result.content_stream,
+            Content::VerificationMethod(method) if method.id == url
+        ));
+
+        let url = DidUrl::from_str(&format!("{did}#missing")).expect("valid DID URL");
+        let res = resolver.dereference(&url).await;
+        assert!(matches!(res, Err(DereferencingError::NotFound)));
+
+        let url = DidUrl::from_str(&format!("{did}?versionId=1")).expect("valid DID URL");
+        let res = resolver.dereference(&url).await;
+        assert!(matches!(
+            res,
+            Err(DereferencingError::Resolution(
+                ResolutionError::InvalidOptions(_)
+            ))
+        ));
+    }
 }
 
 #[cfg(all(test, feature = "did-web"))]
@@ -83,16 +604,25 @@ mod did_web_tests {
     use std::{
         net::SocketAddr,
         str::FromStr,
-        sync::Arc,
+        sync::{
+            Arc,
+            atomic::{
+                AtomicUsize,
+                Ordering,
+            },
+        },
+        time::Duration,
     };
 
     use hyper::{
         Response,
+        StatusCode,
         server::conn::http1::Builder,
         service::service_fn,
     };
     use hyper_util::rt::TokioIo;
     use tokio::net::TcpListener;
+    use xdid_method_web::RetryPolicy;
 
     use super::*;
 
@@ -107,31 +637,167 @@ mod did_web_tests {
         assert_eq!(document.id, did);
     }
 
+    #[tokio::test]
+    async fn test_resolve_did_web_with_metadata() {
+        let did = serve_did_web().await;
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let result = resolver
+            .resolve_with_metadata(&did)
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(result.document.id, did);
+        assert_eq!(
+            result.resolution_metadata.content_type.as_deref(),
+            Some("application/did+json")
+        );
+        assert_eq!(
+            result.resolution_metadata.cache_control(),
+            Some("max-age=60")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builder_web_client() {
+        let did = serve_did_web().await;
+        let client = xdid_method_web::reqwest::Client::builder()
+            .user_agent("xdid-test")
+            .build()
+            .expect("client construction should succeed");
+        let resolver = DidResolver::builder()
+            .with_web_client(client)
+            .build()
+            .expect("resolver construction should succeed");
+        let document = resolver
+            .resolve(&did)
+            .await
+            .expect("resolution should succeed");
+        assert_eq!(document.id, did);
+    }
+
+    #[tokio::test]
+    async fn test_did_web_timeout() {
+        let did = serve(|_, _| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Response::new(String::new())
+        })
+        .await;
+
+        let resolver = DidResolver::builder()
+            .with_web_timeout(Some(Duration::from_millis(100)))
+            .build()
+            .expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_did_web_retry() {
+        let did = serve(|attempt, data| async move {
+            if attempt < 2 {
+                Response::builder()
+                    .status(StatusCode::SERVICE_UNAVAILABLE)
+                    .body(String::new())
+                    .expect("response should build")
+            } else {
+                Response::new(data)
+            }
+        })
+        .await;
+
+        let retry = RetryPolicy {
+            max_retries:     2,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff:     Duration::from_millis(10),
+        };
+
+        let resolver = DidResolver::builder()
+            .with_web_retry(retry)
+            .build()
+            .expect("resolver construction should succeed");
+        resolver
+            .resolve(&did)
+            .await
+            .expect("resolution should succeed after retries");
+
+        let did = serve(|_, _| async {
+            Response::builder()
+                .status(StatusCode::SERVICE_UNAVAILABLE)
+                .body(String::new())
+                .expect("response should build")
+        })
+        .await;
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::InternalError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_did_web_invalid_document() {
+        let did = serve(|_, _| async { Response::new("not a document".to_string()) }).await;
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+
+    #[tokio::test]
+    async fn test_did_web_not_found() {
+        let did = serve(|_, _| async {
+            Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(String::new())
+                .expect("response should build")
+        })
+        .await;
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::NotFound(Some(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_did_web_id_mismatch() {
+        let did =
+            serve(|_, data| async move { Response::new(data.replace("localhost", "127.0.0.1")) })
+                .await;
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver.resolve(&did).await;
+        assert!(matches!(res, Err(ResolutionError::InvalidDidDocument(_))));
+    }
+
     async fn serve_did_web() -> Did {
+        serve(|_, data| async move {
+            Response::builder()
+                .header("content-type", "application/did+json")
+                .header("cache-control", "max-age=60")
+                .body(data)
+                .expect("response should build")
+        })
+        .await
+    }
+
+    /// Serves a did:web DID, responding to each request with `respond`,
+    /// given the number of previous requests and the serialized document.
+    async fn serve<F, Fut>(respond: F) -> Did
+    where
+        F: Fn(usize, String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response<String>> + Send,
+    {
         let port = port_check::free_local_port().expect("free port should be available");
         let addr = SocketAddr::from(([127, 0, 0, 1], port));
         let listener = TcpListener::bind(addr).await.expect("listener should bind");
 
         let did = Did::from_str(&format!("did:web:localhost%3A{port}")).expect("valid DID");
 
-        let doc = Document {
-            id:                    did.clone(),
-            also_known_as:         None,
-            assertion_method:      None,
-            authentication:        None,
-            capability_delegation: None,
-            capability_invocation: None,
-            controller:            None,
-            key_agreement:         None,
-            service:               None,
-            verification_method:   None,
-        };
+        let doc = Document::new(did.clone());
 
-        let data = Arc::new(serde_json::to_string(&doc).expect("serialization should succeed"));
+        let data = serde_json::to_string(&doc).expect("serialization should succeed");
+        let requests = Arc::new(AtomicUsize::new(0));
 
         let handler = move |_| {
-            let data = data.clone();
-            async move { Ok::<_, hyper::Error>(Response::new(data.to_string())) }
+            let attempt = requests.fetch_add(1, Ordering::SeqCst);
+            let res = respond(attempt, data.clone());
+            async move { Ok::<_, hyper::Error>(res.await) }
         };
 
         tokio::spawn(async move {
@@ -0,0 +1,180 @@
+//! Fixtures shared by tests.
+
+// Not every helper is used under every combination of features.
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+        PoisonError,
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    },
+    time::Duration,
+};
+
+use xdid_core::{
+    Method,
+    MethodFuture,
+    ResolutionError,
+    did::Did,
+    document::Document,
+    resolution::{
+        DocumentMetadata,
+        ResolutionMetadata,
+        ResolutionOptions,
+        ResolutionResult,
+    },
+};
+
+/// Constructs the error a [`MethodMock`] fails with.
+pub type MakeError = fn() -> ResolutionError;
+
+/// A configurable [`Method`]. By default, any DID resolves to an empty
+/// document.
+///
+/// The requested `versionId` is echoed in the document metadata, and options
+/// with custom `properties` are rejected.
+#[derive(Clone)]
+pub struct MethodMock {
+    name:              &'static str,
+    documents:         Option<Arc<HashMap<Did, Document>>>,
+    document_metadata: DocumentMetadata,
+    cache_control:     Option<&'static str>,
+    delay:             Duration,
+    error:             Arc<Mutex<Option<MakeError>>>,
+    resolutions:       Arc<AtomicUsize>,
+    running:           Arc<AtomicUsize>,
+    peak:              Arc<AtomicUsize>,
+}
+
+impl MethodMock {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            documents: None,
+            document_metadata: DocumentMetadata::default(),
+            cache_control: None,
+            delay: Duration::ZERO,
+            error: Arc::default(),
+            resolutions: Arc::default(),
+            running: Arc::default(),
+            peak: Arc::default(),
+        }
+    }
+
+    /// Only resolves the given documents, returning `notFound` for others.
+    pub fn with_documents(mut self, documents: impl IntoIterator<Item = Document>) -> Self {
+        self.documents = Some(Arc::new(
+            documents
+                .into_iter()
+                .map(|document| (document.id.clone(), document))
+                .collect(),
+        ));
+        self
+    }
+
+    pub fn with_document_metadata(mut self, document_metadata: DocumentMetadata) -> Self {
+        self.document_metadata = document_metadata;
+        self
+    }
+
+    /// Returns a `Cache-Control` header in the resolution metadata.
+    pub const fn with_cache_control(mut self, cache_control: &'static str) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Waits before each resolution completes.
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Fails every resolution with the given error, until cleared. Applies to
+    /// all clones of the method.
+    pub fn fail_with(&self, error: Option<MakeError>) {
+        *self.error.lock().unwrap_or_else(PoisonError::into_inner) = error;
+    }
+
+    /// Number of resolutions started.
+    pub fn resolutions(&self) -> usize {
+        self.resolutions.load(Ordering::SeqCst)
+    }
+
+    /// Most resolutions running at once.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+impl Method for MethodMock {
+    fn method_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn resolve(&self, did: Did) -> MethodFuture<Result<Document, ResolutionError>> {
+        let fut = self.resolve_with_metadata(did, ResolutionOptions::default());
+        Box::pin(async move { fut.await.map(|result| result.document) })
+    }
+
+    fn resolve_with_metadata(
+        &self,
+        did: Did,
+        options: ResolutionOptions,
+    ) -> MethodFuture<Result<ResolutionResult, ResolutionError>> {
+        let method = self.clone();
+
+        Box::pin(async move {
+            method.resolutions.fetch_add(1, Ordering::SeqCst);
+            let running = method.running.fetch_add(1, Ordering::SeqCst) + 1;
+            method.peak.fetch_max(running, Ordering::SeqCst);
+
+            if method.delay.is_zero() {
+                tokio::task::yield_now().await;
+            } else {
+                tokio::time::sleep(method.delay).await;
+            }
+            method.running.fetch_sub(1, Ordering::SeqCst);
+
+            let error = *method.error.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(error) = error {
+                return Err(error());
+            }
+            if let Some(key) = options.properties.keys().next() {
+                return Err(ResolutionError::InvalidOptions(key.clone()));
+            }
+
+            let document = match &method.documents {
+                Some(documents) => documents
+                    .get(&did)
+                    .cloned()
+                    .ok_or_else(|| ResolutionError::not_found(did.to_string()))?,
+                None => Document::new(did),
+            };
+
+            let mut resolution_metadata = ResolutionMetadata::default();
+            if let Some(cache_control) = method.cache_control {
+                resolution_metadata.properties.insert(
+                    ResolutionMetadata::CACHE_CONTROL.to_string(),
+                    cache_control.into(),
+                );
+            }
+
+            let mut document_metadata = method.document_metadata;
+            if options.version_id.is_some() {
+                document_metadata.version_id = options.version_id;
+            }
+
+            Ok(ResolutionResult {
+                document,
+                resolution_metadata,
+                document_metadata,
+            })
+        })
+    }
+}
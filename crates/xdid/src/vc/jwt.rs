@@ -0,0 +1,549 @@
+//! Credentials secured as JWTs.
+//!
+//! VC Data Model 2.0 credentials are secured with
+//! [VC-JOSE-COSE](https://www.w3.org/TR/vc-jose-cose/#securing-vcs-with-jose):
+//! the credential is the JWT payload, with `typ` set to `vc+jwt`.
+//!
+//! VC Data Model 1.1 credentials are secured as
+//! [JWT-VCs](https://www.w3.org/TR/vc-data-model-1.1/#json-web-token): the
+//! credential is placed in the `vc` claim, alongside registered claims.
+
+use std::time::SystemTime;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+use thiserror::Error;
+use time::{
+    OffsetDateTime,
+    format_description::well_known::Rfc3339,
+};
+use xdid_method_key::DidKeyPair;
+use xdid_vc::{
+    ValidationError,
+    VerifiableCredential,
+    Version,
+};
+
+use crate::{
+    jws::{
+        self,
+        JwsError,
+    },
+    jwt::{
+        self,
+        Claims,
+        JwtError,
+        Validation,
+    },
+    resolver::DidResolver,
+};
+
+/// `typ` header of a VC-JOSE-COSE credential.
+pub const VC_JWT_TYPE: &str = "vc+jwt";
+
+#[derive(Serialize, Deserialize, Default)]
+struct VcClaim<T> {
+    vc: T,
+}
+
+/// Sign a credential as a JWT.
+///
+/// VC Data Model 2.0 credentials are signed as the JWT payload. For VC Data
+/// Model 1.1, the registered claims are derived from the credential: `iss`
+/// from the issuer, `sub` from the subject, `jti` from the id, and `nbf` /
+/// `exp` from the issuance and expiration dates.
+///
+/// The key is not checked against the issuer, as it may be delegated from
+/// another DID document. The caller is responsible for using a key listed
+/// under the issuer's `assertionMethod`, or the credential will fail
+/// verification.
+///
+/// # Errors
+///
+/// Returns an error if the credential is invalid, or signing fails.
+pub fn issue(
+    pair: &impl DidKeyPair,
+    credential: &VerifiableCredential,
+) -> Result<String, VcJwtError> {
+    credential.validate()?;
+
+    if credential.version() == Some(Version::V2) {
+        let header = jws::header(pair, Some(VC_JWT_TYPE))?;
+        let payload = serde_json::to_vec(credential).map_err(VcJwtError::Credential)?;
+        return Ok(jws::sign_with_header(pair, &header, &payload)?);
+    }
+
+    let sub = match credential.credential_subject.as_slice() {
+        [subject] => subject.id.clone(),
+        _ => None,
+    };
+
+    let claims = Claims {
+        iss: Some(credential.issuer.id.clone()),
+        sub,
+        aud: None,
+        exp: credential.expiration_date.and_then(unix_time),
+        nbf: credential.issuance_date.and_then(unix_time),
+        iat: Some(jwt::unix_time(SystemTime::now())),
+        jti: credential.id.clone(),
+        custom: VcClaim { vc: credential },
+    };
+
+    Ok(jwt::issue(pair, &claims)?)
+}
+
+impl DidResolver {
+    /// Verify a credential JWT. The signing key must be a verification method
+    /// of the issuer, listed under the role in `validation` (by default
+    /// `assertionMethod`).
+    ///
+    /// A JWT with `typ` `vc+jwt` must contain a VC Data Model 2.0 credential.
+    /// Any registered claims, such as `exp` or `aud`, are validated as in
+    /// [`Self::verify_jwt`]. Otherwise, it must be a VC Data Model 1.1
+    /// JWT-VC, and properties
+    /// omitted from its `vc` claim are filled in from the registered claims.
+    /// If present, `sub` must match a credential subject.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JWT is invalid, the credential does not match
+    /// its encoding or claims, or the credential is outside its validity
+    /// period.
+    pub async fn verify_credential_jwt(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<VerifiableCredential, VcJwtError> {
+        let unverified = jws::decode(token)?;
+
+        let credential = if unverified.header.typ.as_deref() == Some(VC_JWT_TYPE) {
+            // The issuer is read before verification, to find the signing
+            // key.
+            let issuer = serde_json::from_slice::<VerifiableCredential>(&unverified.payload)
+                .map_err(VcJwtError::Credential)?
+                .issuer
+                .id;
+
+            let jws = self.verify_jws_for(token, &issuer, validation.role).await?;
+
+            // Registered claims may be set alongside the credential.
+            let claims: Claims = serde_json::from_slice(&jws.payload).map_err(JwtError::from)?;
+            claims.validate(validation)?;
+
+            let credential: VerifiableCredential =
+                serde_json::from_slice(&jws.payload).map_err(VcJwtError::Credential)?;
+            credential.validate()?;
+
+            if credential.version() != Some(Version::V2) || credential.issuer.id != issuer {
+                return Err(VcJwtError::VersionMismatch);
+            }
+
+            credential
+        } else {
+            self.verify_vc_claim(token, validation).await?
+        };
+
+        let now = OffsetDateTime::from(validation.now.unwrap_or_else(SystemTime::now));
+
+        if let Some(until) = credential.valid_until()
+            && now >= until + validation.leeway
+        {
+            return Err(VcJwtError::Expired);
+        }
+
+        if let Some(from) = credential.valid_from()
+            && now + validation.leeway < from
+        {
+            return Err(VcJwtError::NotYetValid);
+        }
+
+        Ok(credential)
+    }
+
+    /// Verify a VC Data Model 1.1 JWT-VC, restoring properties from the
+    /// registered claims.
+    async fn verify_vc_claim(
+        &self,
+        token: &str,
+        validation: &Validation,
+    ) -> Result<VerifiableCredential, VcJwtError> {
+        let claims: Claims<VcClaim<Map<String, Value>>> =
+            self.verify_jwt(token, validation).await?;
+        let mut vc = claims.custom.vc;
+
+        if let Some(iss) = &claims.iss {
+            vc.entry("issuer")
+                .or_insert_with(|| Value::String(iss.to_string()));
+        }
+        if let Some(jti) = &claims.jti {
+            vc.entry("id").or_insert_with(|| Value::String(jti.clone()));
+        }
+        if let Some(nbf) = claims.nbf {
+            vc.entry("issuanceDate").or_insert(rfc3339(nbf)?);
+        }
+        if let Some(exp) = claims.exp {
+            vc.entry("expirationDate").or_insert(rfc3339(exp)?);
+        }
+        if let Some(sub) = &claims.sub
+            && let Some(Value::Object(subject)) = vc.get_mut("credentialSubject")
+        {
+            subject
+                .entry("id")
+                .or_insert_with(|| Value::String(sub.to_string()));
+        }
+
+        let credential: VerifiableCredential =
+            serde_json::from_value(Value::Object(vc)).map_err(VcJwtError::Credential)?;
+        credential.validate()?;
+
+        if credential.version() != Some(Version::V1_1) {
+            return Err(VcJwtError::VersionMismatch);
+        }
+
+        if claims.iss.as_ref() != Some(&credential.issuer.id) {
+            return Err(VcJwtError::IssuerMismatch);
+        }
+
+        if let Some(sub) = &claims.sub
+            && !credential
+                .credential_subject
+                .iter()
+                .any(|subject| subject.id.as_ref() == Some(sub))
+        {
+            return Err(VcJwtError::SubjectMismatch);
+        }
+
+        Ok(credential)
+    }
+}
+
+fn unix_time(time: OffsetDateTime) -> Option<u64> {
+    u64::try_from(time.unix_timestamp()).ok()
+}
+
+/// Converts a JWT time claim to an RFC 3339 date-time.
+fn rfc3339(time: u64) -> Result<Value, VcJwtError> {
+    i64::try_from(time)
+        .ok()
+        .and_then(|time| OffsetDateTime::from_unix_timestamp(time).ok())
+        .and_then(|time| time.format(&Rfc3339).ok())
+        .map(Value::String)
+        .ok_or(VcJwtError::InvalidTime)
+}
+
+#[derive(Error, Debug)]
+pub enum VcJwtError {
+    #[error(transparent)]
+    Jws(#[from] JwsError),
+    #[error(transparent)]
+    Jwt(#[from] JwtError),
+    #[error("invalid credential: {0}")]
+    Credential(serde_json::Error),
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    #[error("credential version does not match its JWT encoding")]
+    VersionMismatch,
+    #[error("time claim is out of range")]
+    InvalidTime,
+    #[error("iss claim does not match credential issuer")]
+    IssuerMismatch,
+    #[error("sub claim does not match a credential subject")]
+    SubjectMismatch,
+    #[error("credential expired")]
+    Expired,
+    #[error("credential not yet valid")]
+    NotYetValid,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        time::Duration,
+    };
+
+    use xdid_core::did::Did;
+    use xdid_method_key::{
+        PublicKey,
+        p256::P256KeyPair,
+    };
+    use xdid_vc::{
+        CONTEXT_V1,
+        Context,
+        CredentialSubject,
+    };
+
+    use super::*;
+
+    fn credential(pair: &P256KeyPair) -> VerifiableCredential {
+        let mut claims = Map::new();
+        claims.insert("name".to_string(), Value::String("alice".to_string()));
+
+        let subject = CredentialSubject {
+            id: Some(P256KeyPair::generate().public().to_did()),
+            claims,
+        };
+
+        let mut credential = VerifiableCredential::new(pair.public().to_did(), subject);
+        credential.id = Some("urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5".to_string());
+        credential
+    }
+
+    fn credential_v1(pair: &P256KeyPair) -> VerifiableCredential {
+        let mut credential = credential(pair);
+        credential.context = vec![Context::Url(CONTEXT_V1.into())];
+        credential.issuance_date =
+            Some(OffsetDateTime::from_unix_timestamp(1_700_000_000).expect("valid time"));
+        credential
+    }
+
+    async fn verify(
+        token: &str,
+        validation: &Validation,
+    ) -> Result<VerifiableCredential, VcJwtError> {
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver.verify_credential_jwt(token, validation).await
+    }
+
+    fn sign(pair: &P256KeyPair, claims: &Claims<VcClaim<Value>>) -> String {
+        jwt::issue(pair, claims).expect("issue should succeed")
+    }
+
+    /// Signs a VC-JOSE-COSE credential with additional registered claims.
+    fn sign_vc_jwt(pair: &P256KeyPair, claims: Value) -> String {
+        let mut payload = serde_json::to_value(credential(pair)).expect("serialize");
+        payload
+            .as_object_mut()
+            .expect("object")
+            .extend(serde_json::from_value::<Map<String, Value>>(claims).expect("object"));
+
+        let header = jws::header(pair, Some(VC_JWT_TYPE)).expect("valid header");
+        let payload = serde_json::to_vec(&payload).expect("serialize");
+        jws::sign_with_header(pair, &header, &payload).expect("signing should succeed")
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip() {
+        let pair = P256KeyPair::generate();
+        let credential = credential(&pair);
+
+        let token = issue(&pair, &credential).expect("issue should succeed");
+
+        let unverified = jws::decode(&token).expect("valid JWS");
+        assert_eq!(unverified.header.typ.as_deref(), Some(VC_JWT_TYPE));
+        let payload: Value = serde_json::from_slice(&unverified.payload).expect("valid JSON");
+        assert_eq!(
+            payload,
+            serde_json::to_value(&credential).expect("serialize")
+        );
+
+        let verified = verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+        assert_eq!(verified, credential);
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_v1() {
+        let pair = P256KeyPair::generate();
+        let credential = credential_v1(&pair);
+
+        let token = issue(&pair, &credential).expect("issue should succeed");
+
+        let unverified = jws::decode(&token).expect("valid JWS");
+        let claims: Claims<VcClaim<Value>> =
+            serde_json::from_slice(&unverified.payload).expect("valid claims");
+        assert_eq!(claims.nbf, Some(1_700_000_000));
+
+        let verified = verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+        assert_eq!(verified, credential);
+    }
+
+    #[tokio::test]
+    async fn test_issuer_key_mismatch() {
+        let pair = P256KeyPair::generate();
+        let credential = credential(&P256KeyPair::generate());
+
+        let token = issue(&pair, &credential).expect("issue should succeed");
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::Jws(JwsError::Verify(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let pair = P256KeyPair::generate();
+        let mut credential = credential(&pair);
+        credential.valid_until = Some(OffsetDateTime::now_utc() + time::Duration::HOUR);
+
+        let token = issue(&pair, &credential).expect("issue should succeed");
+        verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+
+        let validation = Validation {
+            now: Some(SystemTime::now() + Duration::from_hours(2)),
+            ..Default::default()
+        };
+        let res = verify(&token, &validation).await;
+        assert!(matches!(res, Err(VcJwtError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_expiry_boundary() {
+        let pair = P256KeyPair::generate();
+        let until = OffsetDateTime::now_utc()
+            .replace_nanosecond(0)
+            .expect("valid time")
+            + time::Duration::HOUR;
+        let mut credential = credential(&pair);
+        credential.valid_until = Some(until);
+
+        let token = issue(&pair, &credential).expect("issue should succeed");
+        let expires = SystemTime::from(until) + Validation::default().leeway;
+
+        let validation = Validation {
+            now: Some(expires - Duration::from_secs(1)),
+            ..Default::default()
+        };
+        verify(&token, &validation)
+            .await
+            .expect("verification should succeed");
+
+        let validation = Validation {
+            now: Some(expires),
+            ..Default::default()
+        };
+        let res = verify(&token, &validation).await;
+        assert!(matches!(res, Err(VcJwtError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_vc_jwt_claims_expired() {
+        let pair = P256KeyPair::generate();
+        let exp = jwt::unix_time(SystemTime::now() - Duration::from_hours(1));
+
+        let token = sign_vc_jwt(&pair, serde_json::json!({ "exp": exp }));
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::Jwt(JwtError::Expired))));
+    }
+
+    #[tokio::test]
+    async fn test_vc_jwt_audience() {
+        let pair = P256KeyPair::generate();
+        let validation = Validation {
+            audience: Some("did:example:verifier".to_string()),
+            ..Default::default()
+        };
+
+        let token = sign_vc_jwt(&pair, serde_json::json!({ "aud": "did:example:verifier" }));
+        verify(&token, &validation)
+            .await
+            .expect("verification should succeed");
+
+        let token = sign_vc_jwt(&pair, serde_json::json!({}));
+        let res = verify(&token, &validation).await;
+        assert!(matches!(
+            res,
+            Err(VcJwtError::Jwt(JwtError::InvalidAudience))
+        ));
+
+        let token = sign_vc_jwt(&pair, serde_json::json!({ "aud": "did:example:other" }));
+        let res = verify(&token, &validation).await;
+        assert!(matches!(
+            res,
+            Err(VcJwtError::Jwt(JwtError::InvalidAudience))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_credential_expired() {
+        let pair = P256KeyPair::generate();
+        let mut credential = credential_v1(&pair);
+        credential.expiration_date = credential
+            .issuance_date
+            .map(|date| date + time::Duration::DAY);
+
+        let mut claims = Claims::<VcClaim<Value>>::new(pair.public().to_did());
+        claims.custom.vc = serde_json::to_value(&credential).expect("serialize");
+
+        let token = sign(&pair, &claims);
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn test_minimal_vc_claim() {
+        let pair = P256KeyPair::generate();
+        let mut credential = credential_v1(&pair);
+        let expiration = OffsetDateTime::now_utc()
+            .replace_nanosecond(0)
+            .expect("valid time")
+            + time::Duration::HOUR;
+        credential.expiration_date = Some(expiration);
+
+        let mut vc = serde_json::to_value(&credential).expect("serialize");
+        let vc_map = vc.as_object_mut().expect("object");
+        vc_map.remove("issuer");
+        vc_map.remove("id");
+        vc_map.remove("issuanceDate");
+        vc_map.remove("expirationDate");
+        vc_map["credentialSubject"]
+            .as_object_mut()
+            .expect("object")
+            .remove("id");
+
+        let mut claims = Claims::<VcClaim<Value>>::new(pair.public().to_did());
+        claims.sub = credential.credential_subject[0].id.clone();
+        claims.jti.clone_from(&credential.id);
+        claims.nbf = Some(1_700_000_000);
+        claims.exp = unix_time(expiration);
+        claims.custom.vc = vc;
+
+        let token = sign(&pair, &claims);
+        let verified = verify(&token, &Validation::default())
+            .await
+            .expect("verification should succeed");
+        assert_eq!(verified, credential);
+    }
+
+    #[tokio::test]
+    async fn test_version_mismatch() {
+        let pair = P256KeyPair::generate();
+
+        let mut claims = Claims::<VcClaim<Value>>::new(pair.public().to_did());
+        claims.custom.vc = serde_json::to_value(credential(&pair)).expect("serialize");
+
+        let token = sign(&pair, &claims);
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::VersionMismatch)));
+
+        let header = jws::header(&pair, Some(VC_JWT_TYPE)).expect("valid header");
+        let payload = serde_json::to_vec(&credential_v1(&pair)).expect("serialize");
+        let token =
+            jws::sign_with_header(&pair, &header, &payload).expect("signing should succeed");
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::VersionMismatch)));
+    }
+
+    #[tokio::test]
+    async fn test_subject_mismatch() {
+        let pair = P256KeyPair::generate();
+        let credential = credential_v1(&pair);
+
+        let mut claims = Claims::<VcClaim<Value>>::new(pair.public().to_did());
+        claims.sub = Some(Did::from_str("did:example:mallory").expect("valid DID"));
+        claims.custom.vc = serde_json::to_value(&credential).expect("serialize");
+
+        let token = sign(&pair, &claims);
+        let res = verify(&token, &Validation::default()).await;
+        assert!(matches!(res, Err(VcJwtError::SubjectMismatch)));
+    }
+}
@@ -0,0 +1,5 @@
+//! [Verifiable Credentials](https://www.w3.org/TR/vc-data-model-2.0/).
+
+pub use xdid_vc::*;
+
+pub mod jwt;
@@ -0,0 +1,306 @@
+//! Resolution of verification methods that reference other DID documents.
+
+use std::collections::HashSet;
+
+use thiserror::Error;
+use xdid_core::{
+    ResolutionError,
+    did::Did,
+    did_url::DidUrl,
+    document::{
+        Document,
+        VerificationMethod,
+        VerificationMethodMap,
+        VerificationRole,
+    },
+};
+
+use crate::resolver::DidResolver;
+
+/// Maximum number of DID documents resolved while following a reference.
+pub const MAX_REFERENCE_DEPTH: usize = 4;
+
+impl DidResolver {
+    /// Resolves a [`VerificationMethod`] to its [`VerificationMethodMap`],
+    /// like [`Document::resolve_verification_method`], but also follows URLs
+    /// that reference other DID documents.
+    ///
+    /// An external method is only accepted if its `controller` is authorized
+    /// by `document`: either the document's own DID, or reachable through its
+    /// `controller` chain within [`MAX_REFERENCE_DEPTH`] documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method cannot be found, a document fails to
+    /// resolve, or its controller is not authorized.
+    pub async fn resolve_verification_method(
+        &self,
+        document: &Document,
+        method: &VerificationMethod,
+    ) -> Result<VerificationMethodMap, ReferenceError> {
+        if let Some(map) = document.resolve_verification_method(method) {
+            return Ok(map);
+        }
+
+        let VerificationMethod::Url(url) = method else {
+            return Err(ReferenceError::NotFound);
+        };
+        if url.did == document.id {
+            return Err(ReferenceError::NotFound);
+        }
+
+        let target = self.resolve(&url.did).await?;
+        let map = target
+            .find_verification_method(url)
+            .ok_or(ReferenceError::NotFound)?;
+
+        self.check_controller(document, &map.controller).await?;
+
+        Ok(map)
+    }
+
+    /// Returns the verification method that the provided [`DidUrl`] is
+    /// referencing, restricted to a given [`VerificationRole`], like
+    /// [`Document::resolve_verification_method_url`], but also following
+    /// references to other DID documents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the method is not listed under the role, a
+    /// document fails to resolve, or its controller is not authorized.
+    pub async fn resolve_verification_method_url(
+        &self,
+        document: &Document,
+        url: &DidUrl,
+        role: VerificationRole,
+    ) -> Result<VerificationMethodMap, ReferenceError> {
+        if let Some(map) = document.resolve_verification_method_url(url, role) {
+            return Ok(map);
+        }
+
+        let methods = match role {
+            VerificationRole::Assertion => document.assertion_method.as_deref(),
+            VerificationRole::Authentication => document.authentication.as_deref(),
+            VerificationRole::CapabilityDelegation => document.capability_delegation.as_deref(),
+            VerificationRole::CapabilityInvocation => document.capability_invocation.as_deref(),
+            VerificationRole::KeyAgreement => document.key_agreement.as_deref(),
+        }
+        .unwrap_or_default();
+
+        let method = methods
+            .iter()
+            .find(
+                |method| matches!(method, VerificationMethod::Url(method_url) if method_url == url),
+            )
+            .ok_or(ReferenceError::NotFound)?;
+
+        self.resolve_verification_method(document, method).await
+    }
+
+    /// Checks that `controller` is the document's DID, or reachable through
+    /// its `controller` chain.
+    async fn check_controller(
+        &self,
+        document: &Document,
+        controller: &Did,
+    ) -> Result<(), ReferenceError> {
+        if document.id == *controller {
+            return Ok(());
+        }
+
+        let mut visited = HashSet::from([document.id.clone()]);
+        let mut frontier = document.controller.clone().unwrap_or_default();
+
+        // The referenced document counts towards the depth.
+        let mut depth = 1;
+
+        loop {
+            frontier.retain(|did| visited.insert(did.clone()));
+
+            if frontier.contains(controller) {
+                return Ok(());
+            }
+            if frontier.is_empty() {
+                return Err(ReferenceError::UnauthorizedController(controller.clone()));
+            }
+            if depth >= MAX_REFERENCE_DEPTH {
+                return Err(ReferenceError::DepthExceeded);
+            }
+            depth += 1;
+
+            let mut next = Vec::new();
+            for did in frontier {
+                next.extend(self.resolve(&did).await?.controller.unwrap_or_default());
+            }
+            frontier = next;
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ReferenceError {
+    #[error("failed to resolve DID: {0}")]
+    Resolution(#[from] ResolutionError),
+    #[error("verification method not found")]
+    NotFound,
+    #[error("controller {0} is not authorized by the referencing document")]
+    UnauthorizedController(Did),
+    #[error("reference depth limit exceeded")]
+    DepthExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use smallvec::SmallVec;
+    use xdid_core::Method;
+
+    use super::*;
+    use crate::test_utils::MethodMock;
+
+    fn did(name: &str) -> Did {
+        Did::from_str(&format!("did:mock:{name}")).expect("valid DID")
+    }
+
+    fn key_url(name: &str) -> DidUrl {
+        DidUrl::from_str(&format!("did:mock:{name}#key")).expect("valid DID URL")
+    }
+
+    fn document(name: &str, controllers: &[&str]) -> Document {
+        let mut document = Document::new(did(name));
+        document.authentication = Some(vec![VerificationMethod::Url(key_url("key-owner"))]);
+        document.controller =
+            (!controllers.is_empty()).then(|| controllers.iter().map(|name| did(name)).collect());
+        document
+    }
+
+    /// Resolver serving `did:mock:key-owner`, which holds `#key` controlled
+    /// by `did:mock:<controller>`, alongside the provided documents.
+    fn resolver(controller: &str, documents: Vec<Document>) -> DidResolver {
+        let mut owner = document("key-owner", &[]);
+        owner.authentication = None;
+        owner.verification_method = Some(vec![VerificationMethodMap {
+            id:                   key_url("key-owner"),
+            controller:           did(controller),
+            typ:                  "Multikey".into(),
+            public_key_jwk:       None,
+            public_key_multibase: Some("z6Mk".to_string()),
+        }]);
+
+        let method = MethodMock::new("mock").with_documents(documents.into_iter().chain([owner]));
+
+        DidResolver {
+            methods: SmallVec::from_vec(vec![Box::new(method) as Box<dyn Method>]),
+        }
+    }
+
+    async fn resolve(
+        resolver: &DidResolver,
+        document: &Document,
+    ) -> Result<VerificationMethodMap, ReferenceError> {
+        resolver
+            .resolve_verification_method_url(
+                document,
+                &key_url("key-owner"),
+                VerificationRole::Authentication,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_same_controller() {
+        let subject = document("subject", &[]);
+        let resolver = resolver("subject", vec![]);
+
+        let map = resolve(&resolver, &subject)
+            .await
+            .expect("method should resolve");
+        assert_eq!(map.id, key_url("key-owner"));
+    }
+
+    #[tokio::test]
+    async fn test_direct_controller() {
+        let subject = document("subject", &["controller"]);
+        let resolver = resolver("controller", vec![]);
+
+        resolve(&resolver, &subject)
+            .await
+            .expect("method should resolve");
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_controller() {
+        let subject = document("subject", &["controller"]);
+        let resolver = resolver("other", vec![document("controller", &[])]);
+
+        let res = resolve(&resolver, &subject).await;
+        assert!(matches!(
+            res,
+            Err(ReferenceError::UnauthorizedController(did)) if did == self::did("other")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_controller_chain() {
+        let subject = document("subject", &["a"]);
+        let resolver = resolver("c", vec![document("a", &["b"]), document("b", &["c"])]);
+
+        resolve(&resolver, &subject)
+            .await
+            .expect("method should resolve");
+    }
+
+    #[tokio::test]
+    async fn test_controller_cycle() {
+        let subject = document("subject", &["a"]);
+        let resolver = resolver(
+            "other",
+            vec![document("a", &["subject"]), document("b", &["a"])],
+        );
+
+        let res = resolve(&resolver, &subject).await;
+        assert!(matches!(
+            res,
+            Err(ReferenceError::UnauthorizedController(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_depth_exceeded() {
+        let subject = document("subject", &["a"]);
+        let resolver = resolver(
+            "e",
+            vec![
+                document("a", &["b"]),
+                document("b", &["c"]),
+                document("c", &["d"]),
+                document("d", &["e"]),
+            ],
+        );
+
+        let res = resolve(&resolver, &subject).await;
+        assert!(matches!(res, Err(ReferenceError::DepthExceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_method() {
+        let subject = document("subject", &[]);
+        let resolver = resolver("subject", vec![]);
+
+        let url = DidUrl::from_str("did:mock:key-owner#missing").expect("valid DID URL");
+        let res = resolver
+            .resolve_verification_method(&subject, &VerificationMethod::Url(url))
+            .await;
+        assert!(matches!(res, Err(ReferenceError::NotFound)));
+
+        let res = resolver
+            .resolve_verification_method_url(
+                &subject,
+                &key_url("key-owner"),
+                VerificationRole::Assertion,
+            )
+            .await;
+        assert!(matches!(res, Err(ReferenceError::NotFound)));
+    }
+}
@@ -0,0 +1,186 @@
+use thiserror::Error;
+use xdid_core::{
+    ResolutionError,
+    did::Did,
+    did_url::DidUrl,
+    document::VerificationRole,
+};
+use xdid_method_key::{
+    DidKeyParser,
+    ParseError,
+    PublicKey,
+};
+
+use crate::{
+    resolver::DidResolver,
+    verification_method::ReferenceError,
+};
+
+impl DidResolver {
+    /// Verify a signature using the verification method referenced by a
+    /// [`DidUrl`]. The DID is resolved, and the method must be listed under
+    /// the given [`VerificationRole`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resolution fails, the method cannot be found, its
+    /// key is unsupported, or the signature is invalid.
+    pub async fn verify(
+        &self,
+        url: &DidUrl,
+        role: VerificationRole,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), VerifyError> {
+        let key = self.resolve_public_key(url, role).await?;
+        key.verify(message, signature)
+            .map_err(VerifyError::InvalidSignature)
+    }
+
+    /// Resolve the public key of the verification method referenced by a
+    /// [`DidUrl`], which must be listed under the given [`VerificationRole`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resolution fails, the method cannot be found, or
+    /// its key is unsupported.
+    pub async fn resolve_public_key(
+        &self,
+        url: &DidUrl,
+        role: VerificationRole,
+    ) -> Result<Box<dyn PublicKey>, VerifyError> {
+        self.resolve_public_key_for(&url.did, url, role).await
+    }
+
+    /// Resolve the public key of the verification method referenced by a
+    /// [`DidUrl`], which must be listed under the given [`VerificationRole`]
+    /// of `did`'s document. The method may belong to another DID document, if
+    /// its controller is authorized by `did`, as in
+    /// [`Self::resolve_verification_method_url`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resolution fails, the method cannot be found or is
+    /// not authorized, or its key is unsupported.
+    pub async fn resolve_public_key_for(
+        &self,
+        did: &Did,
+        url: &DidUrl,
+        role: VerificationRole,
+    ) -> Result<Box<dyn PublicKey>, VerifyError> {
+        let document = self.resolve(did).await?;
+
+        let method = self
+            .resolve_verification_method_url(&document, url, role)
+            .await?;
+
+        let key = DidKeyParser::default().parse_verification_method(&method)?;
+        Ok(key)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum VerifyError {
+    #[error("failed to resolve DID: {0}")]
+    Resolution(#[from] ResolutionError),
+    #[error("verification method not found")]
+    MethodNotFound,
+    #[error(transparent)]
+    Reference(ReferenceError),
+    #[error("unsupported public key: {0}")]
+    UnsupportedKey(#[from] ParseError),
+    #[error("invalid signature: {0}")]
+    InvalidSignature(anyhow::Error),
+}
+
+impl From<ReferenceError> for VerifyError {
+    fn from(value: ReferenceError) -> Self {
+        match value {
+            ReferenceError::Resolution(err) => Self::Resolution(err),
+            ReferenceError::NotFound => Self::MethodNotFound,
+            err => Self::Reference(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use xdid_method_key::{
+        DidKeyPair,
+        PublicKey,
+        Signer,
+        ed25519::Ed25519KeyPair,
+        p256::P256KeyPair,
+    };
+
+    use super::*;
+
+    fn key_url(pair: &impl DidKeyPair) -> DidUrl {
+        let did = pair.public().to_did();
+        DidUrl {
+            fragment: Some(did.method_id.0.clone().into()),
+            did,
+            path_abempty: None,
+            query: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_p256() {
+        let pair = P256KeyPair::generate();
+        let url = key_url(&pair);
+
+        let msg = b"hello";
+        let signature = pair.sign(msg).expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver
+            .verify(&url, VerificationRole::Authentication, msg, &signature)
+            .await
+            .expect("verification should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_verify_ed25519() {
+        let pair = Ed25519KeyPair::generate();
+        let url = key_url(&pair);
+
+        let msg = b"hello";
+        let signature = pair.sign(msg).expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        resolver
+            .verify(&url, VerificationRole::Assertion, msg, &signature)
+            .await
+            .expect("verification should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_verify_wrong_role() {
+        let pair = P256KeyPair::generate();
+        let url = key_url(&pair);
+
+        let msg = b"hello";
+        let signature = pair.sign(msg).expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify(&url, VerificationRole::KeyAgreement, msg, &signature)
+            .await;
+        assert!(matches!(res, Err(VerifyError::MethodNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_verify_invalid_signature() {
+        let pair = P256KeyPair::generate();
+        let url = key_url(&pair);
+
+        let signature = pair.sign(b"hello").expect("signing should succeed");
+
+        let resolver = DidResolver::new().expect("resolver construction should succeed");
+        let res = resolver
+            .verify(&url, VerificationRole::Authentication, b"bye", &signature)
+            .await;
+        assert!(matches!(res, Err(VerifyError::InvalidSignature(_))));
+    }
+}